@@ -0,0 +1,17 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Failures of this day's solvers, returned through
+/// `miette::Result` instead of panicking.
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    InvalidInput { reason: String },
+    #[error("the puzzle has no solution")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    Unsolvable,
+    #[error("the puzzle has more than one answer: {answers:?}")]
+    #[diagnostic(code(aoc::puzzle::ambiguous))]
+    Ambiguous { answers: Vec<String> },
+}
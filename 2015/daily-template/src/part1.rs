@@ -8,8 +8,13 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
+    // Fixtures come from `just examples day-NN`, check the
+    // answer against the puzzle text before trusting it.
     #[rstest]
-    #[case("", "")]
+    #[case(
+        include_str!("../fixtures/example-1.txt"),
+        include_str!("../fixtures/part1-answer.txt")
+    )]
     fn test_process(#[case] input: &str, #[case] result: &str) -> miette::Result<()> {
         assert_eq!(result, process(input)?);
         Ok(())
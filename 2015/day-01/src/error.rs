@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("`{character}` at position {position} is not a parenthesis")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the instructions are only `(` and `)`")
+    )]
+    InvalidInstruction { position: usize, character: char },
+    #[error("Santa never enters the basement")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoBasement,
+}
@@ -1,18 +1,28 @@
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
     let mut count = 0;
-    for c in input.chars() {
-        match c {
-            '(' => count += 1,
-            ')' => count -= 1,
-            _ => unreachable!(),
-        }
+    for (position, c) in input.chars().enumerate() {
+        count += step(position, c)?;
     }
 
     Ok(count.to_string())
 }
 
+/// The floor change of the instruction `c` at `position`.
+pub fn step(position: usize, c: char) -> Result<i32, PuzzleError> {
+    match c {
+        '(' => Ok(1),
+        ')' => Ok(-1),
+        character => Err(PuzzleError::InvalidInstruction {
+            position: position + 1,
+            character,
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +43,16 @@ mod tests {
         assert_eq!(result, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_instruction() {
+        assert_eq!(
+            Err(PuzzleError::InvalidInstruction {
+                position: 3,
+                character: 'x'
+            }),
+            step(2, 'x')
+        );
+        assert!(process("()x").is_err());
+    }
 }
@@ -1,22 +1,17 @@
+use crate::{part1::step, PuzzleError};
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let result = input
-        .chars()
-        .enumerate()
-        .scan(0, |count, (i, c)| {
-            *count += match c {
-                '(' => 1,
-                ')' => -1,
-                _ => unreachable!(),
-            };
-            Some((*count, i + 1))
-        })
-        .find(|(count, _)| *count == -1)
-        .map(|(_, i)| i)
-        .unwrap();
+    let mut count = 0;
+    for (i, c) in input.chars().enumerate() {
+        count += step(i, c)?;
+        if count == -1 {
+            return Ok((i + 1).to_string());
+        }
+    }
 
-    Ok(result.to_string())
+    Err(PuzzleError::NoBasement.into())
 }
 
 #[cfg(test)]
@@ -32,4 +27,13 @@ mod tests {
         assert_eq!(result, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_no_basement() {
+        let error = process("(()").unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::NoBasement),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
 }
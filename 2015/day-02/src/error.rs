@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a present's dimensions, like `2x3x4`")
+    )]
+    InvalidInput { reason: String },
+}
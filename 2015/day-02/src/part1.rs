@@ -2,12 +2,17 @@ use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
     multi::separated_list1,
+    sequence::tuple,
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, presents) = parse(input).map_err(|e| miette::miette!("Parsing failed {}", e))?;
+    let (_input, presents) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let result: u32 = presents
         .iter()
@@ -18,7 +23,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 #[derive(Debug)]
-struct Present {
+pub struct Present {
     l: u32,
     w: u32,
     h: u32,
@@ -31,27 +36,24 @@ impl Present {
     }
 
     fn smallest_side(&self) -> u32 {
-        *[self.l * self.w, self.w * self.h, self.l * self.h]
-            .iter()
-            .min()
-            .unwrap()
+        let Present { l, w, h } = self;
+        (l * w).min(w * h).min(l * h)
     }
 }
 
 fn present(input: &str) -> IResult<&str, Present> {
-    let (input, dimensions) = separated_list1(tag("x"), complete::u32)(input)?;
+    let (input, (l, _, w, _, h)) = tuple((
+        complete::u32,
+        tag("x"),
+        complete::u32,
+        tag("x"),
+        complete::u32,
+    ))(input)?;
 
-    Ok((
-        input,
-        Present {
-            l: dimensions[0],
-            w: dimensions[1],
-            h: dimensions[2],
-        },
-    ))
+    Ok((input, Present { l, w, h }))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Present>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Present>> {
     separated_list1(line_ending, present)(input)
 }
 
@@ -74,6 +76,11 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_missing_dimension() {
+        assert!(process("2x3").is_err());
+    }
+
     #[rstest]
     #[case(Present { l: 2, w: 3, h: 4 }, 6)]
     #[case(Present { l: 1, w: 1, h: 10 }, 1)]
@@ -2,12 +2,17 @@ use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
     multi::separated_list1,
+    sequence::tuple,
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, presents) = parse(input).map_err(|e| miette::miette!("Parsing failed {}", e))?;
+    let (_input, presents) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let result: u32 = presents
         .iter()
@@ -18,7 +23,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 #[derive(Debug)]
-struct Present {
+pub struct Present {
     l: u32,
     w: u32,
     h: u32,
@@ -39,19 +44,18 @@ impl Present {
 }
 
 fn present(input: &str) -> IResult<&str, Present> {
-    let (input, dimensions) = separated_list1(tag("x"), complete::u32)(input)?;
-
-    Ok((
-        input,
-        Present {
-            l: dimensions[0],
-            w: dimensions[1],
-            h: dimensions[2],
-        },
-    ))
+    let (input, (l, _, w, _, h)) = tuple((
+        complete::u32,
+        tag("x"),
+        complete::u32,
+        tag("x"),
+        complete::u32,
+    ))(input)?;
+
+    Ok((input, Present { l, w, h }))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Present>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Present>> {
     separated_list1(line_ending, present)(input)
 }
 
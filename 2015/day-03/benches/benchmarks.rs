@@ -0,0 +1,41 @@
+use std::sync::OnceLock;
+
+use day_03::*;
+
+static INPUT: OnceLock<String> = OnceLock::new();
+
+fn main() -> miette::Result<()> {
+    let Some(input) = aoc_input::local(env!("CARGO_MANIFEST_DIR"))? else {
+        eprintln!("no input.txt, skipping benchmarks");
+        return Ok(());
+    };
+    INPUT.get_or_init(|| input);
+
+    // Run registered benchmarks.
+    divan::main();
+    Ok(())
+}
+
+fn input() -> &'static str {
+    INPUT.get().expect("input to be read before benchmarking")
+}
+
+#[divan::bench]
+fn part1_parse() {
+    part1::parse(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part1() {
+    part1::process(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part2_parse() {
+    part2::parse(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part2() {
+    part2::process(divan::black_box(input())).unwrap();
+}
@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the directions are only `^`, `v`, `<` and `>`")
+    )]
+    InvalidInput { reason: String },
+}
@@ -10,9 +10,13 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, directions) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, directions) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let mut visited: Vec<IVec2> = vec![IVec2::new(0, 0)];
     let mut location_stops = HashMap::from([(visited[0], 1)]);
@@ -31,7 +35,7 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     let (input, directions) = separated_list1(
         line_ending,
         many1(alt((
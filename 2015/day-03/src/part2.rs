@@ -9,9 +9,13 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, directions) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, directions) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let mut santa_pos = IVec2::new(0, 0);
     let mut robo_pos = IVec2::new(0, 0);
@@ -34,7 +38,7 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     let (input, directions) = separated_list1(
         line_ending,
         many1(alt((
@@ -0,0 +1,9 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("no number up to {limit} gives a hash starting with {zeroes} zeroes")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoAdventCoin { zeroes: usize, limit: usize },
+}
@@ -1,15 +1,16 @@
 use rayon::prelude::*;
 
+use crate::{part1::LIMIT, PuzzleError};
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
     const CHUNK_SIZE: usize = 25_000;
 
-    let result = (1..)
-        .into_iter()
+    let result = (1..=LIMIT)
         .step_by(CHUNK_SIZE)
         .find_map(|chunk_start| {
-            (chunk_start..chunk_start + CHUNK_SIZE)
+            (chunk_start..(chunk_start + CHUNK_SIZE).min(LIMIT + 1))
                 .into_par_iter()
                 .find_first(|&i| {
                     let mut buffer = String::with_capacity(input.len() + 20);
@@ -21,7 +22,10 @@ pub fn process(input: &str) -> miette::Result<String> {
                     format!("{:x}", hash).starts_with("000000")
                 })
         })
-        .expect("A solution exists");
+        .ok_or(PuzzleError::NoAdventCoin {
+            zeroes: 6,
+            limit: LIMIT,
+        })?;
 
     Ok(result.to_string())
 }
@@ -0,0 +1,9 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("line {line} is not a string of lowercase letters: `{text}`")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    InvalidString { line: usize, text: String },
+}
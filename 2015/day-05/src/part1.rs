@@ -1,7 +1,10 @@
 use itertools::Itertools;
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
+    check(input)?;
 
     let result = input
         .lines()
@@ -23,6 +26,22 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+/// Fails on the first line that is not all lowercase
+/// letters, which the rules are written for.
+pub fn check(input: &str) -> Result<(), PuzzleError> {
+    match input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| !line.bytes().all(|b| b.is_ascii_lowercase()))
+    {
+        Some((i, line)) => Err(PuzzleError::InvalidString {
+            line: i + 1,
+            text: line.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +66,15 @@ ooo",
         assert_eq!(result, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_string() {
+        assert_eq!(
+            Err(PuzzleError::InvalidString {
+                line: 2,
+                text: "Aaa".to_string()
+            }),
+            check("aaa\nAaa")
+        );
+    }
 }
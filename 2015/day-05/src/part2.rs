@@ -1,5 +1,8 @@
+use crate::part1::check;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
+    check(input)?;
 
     let result = input
         .lines()
@@ -1,9 +1,27 @@
- use day_06::part1::process;
- use miette::Context;
+use day_06::part1::{process, process_frames};
+use miette::Context;
+use simulation::image::Recorder;
 
- fn main() -> miette::Result<()> {
-     let file = include_str!("../../input1.txt");
-     let result = process(file).context("process part 1")?;
-     println!("{}", result);
-     Ok(())
- }
+fn main() -> miette::Result<()> {
+    let args = aoc_input::Args::from_env(env!("CARGO_MANIFEST_DIR"))?;
+    let file = args.source.read()?;
+    let result = match &args.frames {
+        // One frame per instruction, a pixel per light.
+        Some(dir) => {
+            let mut recorder = Recorder::create(dir)?;
+            let result = process_frames(&file, |lights| {
+                Ok(recorder.record(
+                    lights,
+                    |&on| if on { [255, 220, 120] } else { [10, 12, 30] },
+                )?)
+            })
+            .context("process part 1")?;
+            let frames = recorder.finish()?;
+            eprintln!("wrote {frames} frames to {}", dir.display());
+            result
+        }
+        None => process(&file).context("process part 1")?,
+    };
+    println!("{}", result);
+    Ok(())
+}
@@ -1,10 +1,28 @@
- use day_06::part2::process;
- use miette::Context;
+use day_06::part2::{process, process_frames};
+use miette::Context;
+use simulation::image::{Recorder, Rgb};
 
- fn main() -> miette::Result<()> {
-     let file = include_str!("../../input2.txt");
-     let result = process(file).context("process part 2")?;
-     println!("{}", result);
-     Ok(())
- }
+fn main() -> miette::Result<()> {
+    let args = aoc_input::Args::from_env(env!("CARGO_MANIFEST_DIR"))?;
+    let file = args.source.read()?;
+    let result = match &args.frames {
+        // One frame per instruction, a pixel per light.
+        Some(dir) => {
+            let mut recorder = Recorder::create(dir)?;
+            let result = process_frames(&file, |lights| Ok(recorder.record(lights, brightness)?))
+                .context("process part 2")?;
+            let frames = recorder.finish()?;
+            eprintln!("wrote {frames} frames to {}", dir.display());
+            result
+        }
+        None => process(&file).context("process part 2")?,
+    };
+    println!("{}", result);
+    Ok(())
+}
 
+/// Brighter lights in lighter shades, saturating at 50.
+fn brightness(&light: &u32) -> Rgb {
+    let level = (light.min(50) * 5) as u8;
+    [level, level, level / 2 + 10]
+}
@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    InvalidInput { reason: String },
+    #[error("light {x},{y} is outside of the {size}x{size} grid")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("lights are numbered from 0,0 to {}", size - 1)
+    )]
+    OutOfBounds { x: i32, y: i32, size: usize },
+}
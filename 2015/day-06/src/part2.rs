@@ -9,14 +9,43 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
+/// Lights on each side of the grid.
+const SIZE: usize = 1000;
+
 pub fn process(input: &str) -> miette::Result<String> {
+    solve(input, |_| Ok(()))
+}
+
+/// Solves the puzzle, showing `frame` the lights before the
+/// first instruction and after every one.
+pub fn process_frames(
+    input: &str,
+    mut frame: impl FnMut(&grid::Grid<u32>) -> miette::Result<()>,
+) -> miette::Result<String> {
+    solve(input, |grid| frame(&grid.lights()))
+}
+
+fn solve(
+    input: &str,
+    mut frame: impl FnMut(&Grid) -> miette::Result<()>,
+) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, instructions) = parse(input).map_err(|e| miette::miette!("Parsing error {e}"))?;
+    let (_input, instructions) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
+
+    for instruction in &instructions {
+        instruction.check(SIZE)?;
+    }
 
-    let mut grid = Grid::new(1000, 1000);
+    let mut grid = Grid::new(SIZE, SIZE);
+    frame(&grid)?;
 
     for instruction in instructions {
         grid.apply_instruction(&instruction);
+        frame(&grid)?;
     }
 
     let result = grid.count_lit();
@@ -39,6 +68,12 @@ impl Grid {
         }
     }
 
+    /// The brightness of each light, as a copy.
+    fn lights(&self) -> grid::Grid<u32> {
+        grid::Grid::from_vec(self.width, self.height, self.lights.clone())
+            .expect("lights to fill the grid")
+    }
+
     fn get_index(&self, x: i32, y: i32) -> usize {
         (y as usize * self.width) + x as usize
     }
@@ -71,12 +106,33 @@ impl Grid {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     TurnOn(IVec2, IVec2),
     TurnOff(IVec2, IVec2),
     Toggle(IVec2, IVec2),
 }
 
+impl Instruction {
+    /// Fails when a corner is outside of a grid of `size`
+    /// by `size` lights.
+    fn check(&self, size: usize) -> Result<(), PuzzleError> {
+        let (Instruction::TurnOn(start, end)
+        | Instruction::TurnOff(start, end)
+        | Instruction::Toggle(start, end)) = self;
+        for corner in [start, end] {
+            let inside = |n: i32| usize::try_from(n).is_ok_and(|n| n < size);
+            if !inside(corner.x) || !inside(corner.y) {
+                return Err(PuzzleError::OutOfBounds {
+                    x: corner.x,
+                    y: corner.y,
+                    size,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 fn parse_num(input: &str) -> IResult<&str, i32> {
     map_res(digit1, str::parse)(input)
 }
@@ -115,7 +171,7 @@ fn parse_toggle(input: &str) -> IResult<&str, Instruction> {
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
     separated_list1(
         line_ending,
         alt((parse_turn_on, parse_turn_off, parse_toggle)),
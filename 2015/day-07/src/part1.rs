@@ -1,181 +1,98 @@
-use std::collections::HashMap;
-
+use aoc_parse::{lines, IResult, ParseError, Span};
+use netlist::{Gate, Netlist, NetlistError, Signal};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
-    character::complete::{digit1, line_ending, multispace0, multispace1},
-    combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::tuple,
-    IResult,
+    bytes::complete::take_while1,
+    character::complete::{self, multispace0, multispace1},
+    combinator::map,
+    sequence::{delimited, preceded, separated_pair, tuple},
+    Parser,
 };
+use nom_supreme::{tag::complete::tag, ParserExt};
 
 pub fn process(input: &str, wire: Option<&str>) -> miette::Result<String> {
     let input = input.trim();
 
-    let (_input, instructions) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
-
-    let mut circuit = Circuit::new(instructions);
-
-    if let Some(targ_wire) = wire {
-        let result = circuit.evaluate(targ_wire);
-        Ok(result.to_string())
-    } else {
-        let result = circuit.evaluate("a");
-        Ok(result.to_string())
-    }
-}
+    let circuit = circuit(parse(input)?)?;
 
-#[derive(Debug)]
-struct Circuit {
-    instructions: HashMap<String, Operation>,
-    cache: HashMap<String, u16>,
+    let result = circuit.value(wire.unwrap_or("a"))?;
+    Ok(result.to_string())
 }
 
-impl Circuit {
-    fn new(instructions: HashMap<String, Operation>) -> Self {
-        Circuit {
-            instructions,
-            cache: HashMap::new(),
-        }
-    }
-
-    fn evaluate(&mut self, wire: &str) -> u16 {
-        if let Some(&value) = self.cache.get(wire) {
-            return value;
-        }
-
-        let operation = self.instructions.get(wire).expect("Wire not found").clone();
-
-        let value = match operation {
-            Operation::Direct(source) => self.evaluate_source(&source),
-            Operation::Not(source) => !self.evaluate_source(&source),
-            Operation::And(source1, source2) => {
-                self.evaluate_source(&source1) & self.evaluate_source(&source2)
-            }
-            Operation::Or(source1, source2) => {
-                self.evaluate_source(&source1) | self.evaluate_source(&source2)
-            }
-            Operation::LSHIFT(source, bits) => self.evaluate_source(&source) << bits,
-            Operation::RSHIFT(source, bits) => self.evaluate_source(&source) >> bits,
-        };
-
-        self.cache.insert(wire.to_string(), value);
-        value
-    }
-
-    fn evaluate_source(&mut self, source: &Source) -> u16 {
-        match source {
-            Source::Wire(wire) => self.evaluate(wire),
-            Source::Value(val) => *val,
-        }
+/// Connects every instruction into a circuit of 16-bit
+/// wires.
+fn circuit(instructions: Vec<(String, Gate)>) -> Result<Netlist, NetlistError> {
+    let mut netlist = Netlist::new(16)?;
+    for (wire, gate) in instructions {
+        netlist.connect(wire, gate)?;
     }
-}
-
-#[derive(Debug, Clone)]
-enum Source {
-    Wire(String),
-    Value(u16),
-}
-
-#[derive(Debug, Clone)]
-enum Operation {
-    Direct(Source),
-    And(Source, Source),
-    Or(Source, Source),
-    Not(Source),
-    LSHIFT(Source, u16),
-    RSHIFT(Source, u16),
+    Ok(netlist)
 }
 
 // PARSING
-fn parse_num(input: &str) -> IResult<&str, u16> {
-    map_res(digit1, str::parse)(input)
+fn parse_num(input: Span) -> IResult<u16> {
+    complete::u16.context("number").parse(input)
 }
 
-fn parse_wire_name(input: &str) -> IResult<&str, String> {
-    map(take_while1(|c: char| c.is_lowercase()), |s: &str| {
-        s.to_string()
-    })(input)
+fn parse_wire_name(input: Span) -> IResult<String> {
+    take_while1(|c: char| c.is_lowercase())
+        .map(|s: Span| s.fragment().to_string())
+        .context("wire name")
+        .parse(input)
 }
 
-fn parse_source(input: &str) -> IResult<&str, Source> {
+fn parse_source(input: Span) -> IResult<Signal> {
     alt((
-        map(parse_wire_name, Source::Wire),
-        map(parse_num, Source::Value),
+        map(parse_wire_name, Signal::Wire),
+        map(parse_num, |value| Signal::Value(value.into())),
     ))(input)
 }
+
+fn parse_arrow(input: Span) -> IResult<Span> {
+    delimited(multispace0, tag("->"), multispace0)(input)
+}
 // Direct assignment: "123 -> x" or "y -> x"
-fn parse_direct(input: &str) -> IResult<&str, (String, Operation)> {
+fn parse_direct(input: Span) -> IResult<(String, Gate)> {
     map(
-        tuple((
-            parse_source,
-            multispace0,
-            tag("->"),
-            multispace0,
-            parse_wire_name,
-        )),
-        |(src, _, _, _, dest)| (dest, Operation::Direct(src)),
+        separated_pair(parse_source, parse_arrow, parse_wire_name),
+        |(src, dest)| (dest, Gate::Buffer(src)),
     )(input)
 }
 // Binary operations: "x AND y -> z", "x LSHIFT 2 -> z"
-fn parse_binary(input: &str) -> IResult<&str, (String, Operation)> {
-    map(
-        tuple((
-            parse_source,
-            multispace0,
-            alt((tag("AND"), tag("OR"), tag("LSHIFT"), tag("RSHIFT"))),
-            multispace0,
-            parse_source,
-            multispace0,
-            tag("->"),
-            multispace0,
-            parse_wire_name,
-        )),
-        |(src1, _, op, _, src2, _, _, _, dest)| {
-            let operation = match op {
-                "AND" => Operation::And(src1, src2),
-                "OR" => Operation::Or(src1, src2),
-                "LSHIFT" => {
-                    if let Source::Value(n) = src2 {
-                        Operation::LSHIFT(src1, n)
-                    } else {
-                        panic!("LSHIFT requires a numeric value")
-                    }
-                }
-                "RSHIFT" => {
-                    if let Source::Value(n) = src2 {
-                        Operation::RSHIFT(src1, n)
-                    } else {
-                        panic!("RSHIFT requires a numeric value")
-                    }
-                }
-                _ => unreachable!(),
-            };
-            (dest, operation)
-        },
-    )(input)
+fn parse_binary(input: Span) -> IResult<(String, Gate)> {
+    let gate = |name: &'static str| delimited(multispace1, tag(name), multispace1);
+
+    let (input, src1) = parse_source(input)?;
+    // Shifts only take a number, so a wire after them is
+    // reported where it is instead of failing later.
+    let (input, operation) = alt((
+        preceded(gate("AND"), parse_source).map(|src2| Gate::And(src1.clone(), src2)),
+        preceded(gate("OR"), parse_source).map(|src2| Gate::Or(src1.clone(), src2)),
+        preceded(gate("LSHIFT"), parse_num).map(|bits| Gate::Shl(src1.clone(), bits.into())),
+        preceded(gate("RSHIFT"), parse_num).map(|bits| Gate::Shr(src1.clone(), bits.into())),
+    ))(input)?;
+    let (input, dest) = preceded(parse_arrow, parse_wire_name)(input)?;
+
+    Ok((input, (dest, operation)))
 }
 // NOT operation: "NOT x -> y"
-fn parse_unary(input: &str) -> IResult<&str, (String, Operation)> {
+fn parse_unary(input: Span) -> IResult<(String, Gate)> {
     map(
-        tuple((
-            tag("NOT"),
-            multispace1,
-            parse_source,
-            multispace0,
-            tag("->"),
-            multispace0,
+        separated_pair(
+            preceded(tuple((tag("NOT"), multispace1)), parse_source),
+            parse_arrow,
             parse_wire_name,
-        )),
-        |(_, _, src, _, _, _, dest)| (dest, Operation::Not(src)),
+        ),
+        |(src, dest)| (dest, Gate::Not(src)),
     )(input)
 }
-fn parse(input: &str) -> IResult<&str, HashMap<String, Operation>> {
-    map(
-        separated_list1(line_ending, alt((parse_direct, parse_binary, parse_unary))),
-        |instructions| instructions.into_iter().collect(),
-    )(input)
+
+fn parse_instructions(input: Span) -> IResult<Vec<(String, Gate)>> {
+    lines(alt((parse_direct, parse_binary, parse_unary)).context("instruction")).parse(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<(String, Gate)>, ParseError> {
+    aoc_parse::parse(input, parse_instructions)
 }
 
 #[cfg(test)]
@@ -209,4 +126,17 @@ NOT y -> i",
         assert_eq!(result, process(input, Some("i"))?);
         Ok(())
     }
+
+    #[rstest]
+    #[case("x -> a", NetlistError::Dangling { wire: "a".to_string(), input: "x".to_string() })]
+    #[case("b -> a\na -> b", NetlistError::Cycle { wires: vec!["a".to_string(), "b".to_string(), "a".to_string()] })]
+    #[case("1 -> a\n2 -> a", NetlistError::MultipleDrivers { wire: "a".to_string() })]
+    fn test_invalid_circuit(
+        #[case] input: &str,
+        #[case] error: NetlistError,
+    ) -> miette::Result<()> {
+        let result = circuit(parse(input)?).and_then(|circuit| circuit.value("a"));
+        assert_eq!(Err(error), result);
+        Ok(())
+    }
 }
@@ -2,8 +2,8 @@
  use miette::Context;
 
  fn main() -> miette::Result<()> {
-     let file = include_str!("../../input2.txt");
-     let result = process(file).context("process part 2")?;
+     let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+     let result = process(&file).context("process part 2")?;
      println!("{}", result);
      Ok(())
  }
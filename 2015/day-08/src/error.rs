@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("line {line} is not a string literal: `{text}`")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line starts and ends with a double quote")
+    )]
+    NotALiteral { line: usize, text: String },
+}
@@ -13,11 +13,14 @@
 //! difference increases by three. The sequences `\\` and `\"` both increase the difference by one.
 //! Each newline increases the difference by two since every line is enclosed with two quotes.
 
+use crate::PuzzleError;
+
 const NEWLINE: u8 = 10;
 const SLASH: u8 = 92;
 const ESCAPE: u8 = 120;
 
 pub fn process(input: &str) -> miette::Result<String> {
+    check(input)?;
     let (_, result) = input
         .bytes()
         .fold((false, 0), |(flag, count), b| match (flag, b) {
@@ -30,3 +33,19 @@ pub fn process(input: &str) -> miette::Result<String> {
 
     Ok(result.to_string())
 }
+
+/// Fails on the first line that is not quoted, as both
+/// parts count on every line being a string literal.
+pub fn check(input: &str) -> Result<(), PuzzleError> {
+    match input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.len() < 2 || !line.starts_with('"') || !line.ends_with('"'))
+    {
+        Some((i, line)) => Err(PuzzleError::NotALiteral {
+            line: i + 1,
+            text: line.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
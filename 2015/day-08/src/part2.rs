@@ -8,12 +8,14 @@
 //! need to be escaped so increase the difference by one. As before each newline increases by the
 //! difference by two.
 
+use crate::part1::check;
+
 const NEWLINE: u8 = 10;
 const QUOTE: u8 = 34;
 const SLASH: u8 = 92;
-const ESCAPE: u8 = 120;
 
 pub fn process(input: &str) -> miette::Result<String> {
+    check(input)?;
     let result: usize = input
         .bytes()
         .map(|b| match b {
@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a distance, like `London to Dublin = 464`")
+    )]
+    InvalidInput { reason: String },
+    #[error("no route visits every city")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoRoute,
+}
@@ -3,21 +3,25 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, newline},
+    character::complete::{self, alpha1, newline},
     multi::separated_list1,
     sequence::tuple,
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let (_input, route_map) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, route_map) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     route_map
         .find_path_length(true)
         .map(|d| d.to_string())
-        .ok_or_else(|| miette::miette!("No valid path found"))
+        .ok_or_else(|| PuzzleError::NoRoute.into())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -29,7 +33,7 @@ struct Route {
 }
 
 #[derive(Debug, Default)]
-struct RouteMap<'a> {
+pub struct RouteMap<'a> {
     routes: HashMap<City<'a>, HashMap<City<'a>, Route>>,
 }
 
@@ -87,14 +91,18 @@ fn parse_city(input: &str) -> IResult<&str, City> {
 }
 
 fn parse_route(input: &str) -> IResult<&str, (City, City, Route)> {
-    let (input, (from, _, to, _, distance)) =
-        tuple((parse_city, tag(" to "), parse_city, tag(" = "), digit1))(input)?;
+    let (input, (from, _, to, _, distance)) = tuple((
+        parse_city,
+        tag(" to "),
+        parse_city,
+        tag(" = "),
+        complete::u32,
+    ))(input)?;
 
-    let distance = distance.parse().unwrap();
     Ok((input, (from, to, Route { distance })))
 }
 
-fn parse(input: &str) -> IResult<&str, RouteMap> {
+pub fn parse(input: &str) -> IResult<&str, RouteMap> {
     let (_input, routes) = separated_list1(newline, parse_route)(input)?;
 
     let mut route_map = RouteMap::new();
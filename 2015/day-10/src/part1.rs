@@ -1,14 +1,25 @@
 use std::iter::from_fn;
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let result = look_and_say(input, 40).unwrap().len();
+    let result = look_and_say(input, 40)?.len();
 
     Ok(result.to_string())
 }
 
 fn look_and_say(input: &str, iterations: usize) -> miette::Result<String> {
+    if let Some((position, character)) =
+        input.chars().enumerate().find(|(_, c)| !c.is_ascii_digit())
+    {
+        return Err(PuzzleError::InvalidDigit {
+            position: position + 1,
+            character,
+        }
+        .into());
+    }
     let mut current = input.to_string();
 
     for _ in 0..iterations {
@@ -79,4 +90,16 @@ mod tests {
         assert_eq!(look_and_say("1", 4).unwrap(), "111221");
         // "1" -> "11" -> "21" -> "1211" -> "111221"
     }
+
+    #[test]
+    fn test_invalid_digit() {
+        let error = look_and_say("12a", 1).unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::InvalidDigit {
+                position: 3,
+                character: 'a'
+            }),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
 }
@@ -1,14 +1,25 @@
 use std::iter::from_fn;
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let result = look_and_say(input, 50).unwrap().len();
+    let result = look_and_say(input, 50)?.len();
 
     Ok(result.to_string())
 }
 
 fn look_and_say(input: &str, iterations: usize) -> miette::Result<String> {
+    if let Some((position, character)) =
+        input.chars().enumerate().find(|(_, c)| !c.is_ascii_digit())
+    {
+        return Err(PuzzleError::InvalidDigit {
+            position: position + 1,
+            character,
+        }
+        .into());
+    }
     let mut current = input.to_string();
 
     for _ in 0..iterations {
@@ -80,6 +91,3 @@ mod tests {
         // "1" -> "11" -> "21" -> "1211" -> "111221"
     }
 }
-
-
-
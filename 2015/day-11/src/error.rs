@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("`{password}` is not a password")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("passwords are exactly 8 lowercase letters")
+    )]
+    InvalidPassword { password: String },
+    #[error("no valid password comes after `{password}`")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoNextPassword { password: String },
+}
@@ -1,15 +1,22 @@
+use std::fmt;
+
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
     let first_password = next_password(input)?;
-    let result = next_password(&first_password);
-
-    Ok(result.unwrap())
+    next_password(&first_password)
 }
 
 fn next_password(input: &str) -> miette::Result<String> {
-    let mut password = Password::from_str(input).unwrap();
-    password.increment();
+    let mut password = Password::from_str(input)?;
+    if !password.increment() {
+        return Err(PuzzleError::NoNextPassword {
+            password: input.to_string(),
+        }
+        .into());
+    }
 
     loop {
         for pos in 0..8 {
@@ -17,7 +24,12 @@ fn next_password(input: &str) -> miette::Result<String> {
         }
 
         if !password.is_valid() {
-            password.increment();
+            if !password.increment() {
+                return Err(PuzzleError::NoNextPassword {
+                    password: input.to_string(),
+                }
+                .into());
+            }
         } else {
             break;
         }
@@ -30,18 +42,13 @@ fn next_password(input: &str) -> miette::Result<String> {
 struct Password([u8; 8]);
 
 impl Password {
-    fn from_str(s: &str) -> miette::Result<Self, &'static str> {
-        if s.len() != 8 || !s.chars().all(|c| c.is_ascii_lowercase()) {
-            return Err("Password must be 8 lowercase ASCII characters");
+    fn from_str(s: &str) -> Result<Self, PuzzleError> {
+        match s.as_bytes().try_into() {
+            Ok(bytes) if s.chars().all(|c| c.is_ascii_lowercase()) => Ok(Password(bytes)),
+            _ => Err(PuzzleError::InvalidPassword {
+                password: s.to_string(),
+            }),
         }
-
-        let bytes: Vec<u8> = s.bytes().collect();
-
-        Ok(Password(bytes.try_into().unwrap()))
-    }
-
-    fn to_string(&self) -> String {
-        String::from_utf8(self.0.to_vec()).unwrap()
     }
 
     // RULES
@@ -80,16 +87,19 @@ impl Password {
         self.has_increasing_straight() && !self.has_forbidden_letters() && self.has_two_pairs()
     }
 
-    fn increment(&mut self) {
+    /// Moves on to the next password, `false` when it was
+    /// the last one, `zzzzzzzz`.
+    fn increment(&mut self) -> bool {
         for i in (0..8).rev() {
             let current = self.0[i];
 
             self.0[i] = if current == b'z' { b'a' } else { current + 1 };
 
             if current != b'z' {
-                break;
+                return true;
             }
         }
+        false
     }
 
     fn skip_forbidden(&mut self, position: usize) {
@@ -112,6 +122,14 @@ impl Password {
     }
 }
 
+impl fmt::Display for Password {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|&b| write!(f, "{}", char::from(b)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
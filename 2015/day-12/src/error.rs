@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the input is a JSON document of arrays, objects, strings and whole numbers")
+    )]
+    InvalidInput { reason: String },
+}
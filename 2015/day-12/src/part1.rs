@@ -2,21 +2,25 @@ use nom::{
     branch::alt,
     bytes::complete::is_not,
     character::complete::{self, char},
-    combinator::{map, opt, recognize},
+    combinator::{map, map_res, opt, recognize},
     multi::separated_list0,
     sequence::{delimited, pair, separated_pair},
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, json_val) = parse_val(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, json_val) = parse_val(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     Ok(json_val.sum().to_string())
 }
 
 #[derive(Debug, PartialEq)]
-enum JsonVal {
+pub enum JsonVal {
     Number(i64),
     Array(Vec<JsonVal>),
     Object(Vec<(String, JsonVal)>),
@@ -34,22 +38,26 @@ impl JsonVal {
     }
 }
 
-fn parse_val(input: &str) -> IResult<&str, JsonVal> {
+pub fn parse_val(input: &str) -> IResult<&str, JsonVal> {
     alt((parse_num, parse_array, parse_obj, parse_string))(input)
 }
 
 fn parse_num(input: &str) -> IResult<&str, JsonVal> {
     map(
-        recognize(pair(opt(char('-')), complete::digit1)),
-        |num_str: &str| JsonVal::Number(num_str.parse().unwrap()),
+        map_res(
+            recognize(pair(opt(char('-')), complete::digit1)),
+            str::parse,
+        ),
+        JsonVal::Number,
     )(input)
 }
 
+fn parse_str(input: &str) -> IResult<&str, &str> {
+    delimited(complete::char('"'), is_not("\""), complete::char('"'))(input)
+}
+
 fn parse_string(input: &str) -> IResult<&str, JsonVal> {
-    map(
-        delimited(complete::char('"'), is_not("\""), complete::char('"')),
-        |s: &str| JsonVal::String(s.to_string()),
-    )(input)
+    map(parse_str, |s| JsonVal::String(s.to_string()))(input)
 }
 
 fn parse_array(input: &str) -> IResult<&str, JsonVal> {
@@ -69,7 +77,7 @@ fn parse_obj(input: &str) -> IResult<&str, JsonVal> {
             complete::char('{'),
             separated_list0(
                 complete::char(','),
-                separated_pair(parse_string, complete::char(':'), parse_val),
+                separated_pair(parse_str, complete::char(':'), parse_val),
             ),
             complete::char('}'),
         ),
@@ -77,13 +85,7 @@ fn parse_obj(input: &str) -> IResult<&str, JsonVal> {
             JsonVal::Object(
                 pairs
                     .into_iter()
-                    .map(|(key, val)| {
-                        if let JsonVal::String(k) = key {
-                            (k, val)
-                        } else {
-                            panic!("Object key must be a string")
-                        }
-                    })
+                    .map(|(key, val)| (key.to_string(), val))
                     .collect(),
             )
         },
@@ -118,6 +120,7 @@ mod parser_tests {
     fn test_num_parser() {
         assert_eq!(parse_num("123"), Ok(("", JsonVal::Number(123))));
         assert_eq!(parse_num("-123"), Ok(("", JsonVal::Number(-123))));
+        assert!(parse_num("99999999999999999999").is_err());
     }
 
     #[test]
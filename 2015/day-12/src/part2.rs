@@ -2,21 +2,25 @@ use nom::{
     branch::alt,
     bytes::complete::is_not,
     character::complete::{self, char},
-    combinator::{map, opt, recognize},
+    combinator::{map, map_res, opt, recognize},
     multi::separated_list0,
     sequence::{delimited, pair, separated_pair},
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, json_val) = parse_val(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, json_val) = parse_val(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     Ok(json_val.sum().to_string())
 }
 
 #[derive(Debug, PartialEq)]
-enum JsonVal {
+pub enum JsonVal {
     Number(i64),
     Array(Vec<JsonVal>),
     Object(Vec<(String, JsonVal)>),
@@ -43,22 +47,26 @@ impl JsonVal {
     }
 }
 
-fn parse_val(input: &str) -> IResult<&str, JsonVal> {
+pub fn parse_val(input: &str) -> IResult<&str, JsonVal> {
     alt((parse_num, parse_array, parse_obj, parse_string))(input)
 }
 
 fn parse_num(input: &str) -> IResult<&str, JsonVal> {
     map(
-        recognize(pair(opt(char('-')), complete::digit1)),
-        |num_str: &str| JsonVal::Number(num_str.parse().unwrap()),
+        map_res(
+            recognize(pair(opt(char('-')), complete::digit1)),
+            str::parse,
+        ),
+        JsonVal::Number,
     )(input)
 }
 
+fn parse_str(input: &str) -> IResult<&str, &str> {
+    delimited(complete::char('"'), is_not("\""), complete::char('"'))(input)
+}
+
 fn parse_string(input: &str) -> IResult<&str, JsonVal> {
-    map(
-        delimited(complete::char('"'), is_not("\""), complete::char('"')),
-        |s: &str| JsonVal::String(s.to_string()),
-    )(input)
+    map(parse_str, |s| JsonVal::String(s.to_string()))(input)
 }
 
 fn parse_array(input: &str) -> IResult<&str, JsonVal> {
@@ -78,7 +86,7 @@ fn parse_obj(input: &str) -> IResult<&str, JsonVal> {
             complete::char('{'),
             separated_list0(
                 complete::char(','),
-                separated_pair(parse_string, complete::char(':'), parse_val),
+                separated_pair(parse_str, complete::char(':'), parse_val),
             ),
             complete::char('}'),
         ),
@@ -86,13 +94,7 @@ fn parse_obj(input: &str) -> IResult<&str, JsonVal> {
             JsonVal::Object(
                 pairs
                     .into_iter()
-                    .map(|(key, val)| {
-                        if let JsonVal::String(k) = key {
-                            (k, val)
-                        } else {
-                            panic!("Object key must be a string")
-                        }
-                    })
+                    .map(|(key, val)| (key.to_string(), val))
                     .collect(),
             )
         },
@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is like `Alice would gain 54 happiness units by sitting next to Bob.`")
+    )]
+    InvalidInput { reason: String },
+}
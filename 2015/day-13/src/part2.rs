@@ -10,10 +10,13 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, relationships) =
-        parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, relationships) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let mut seating = SeatingArrangement::from_relationships(relationships);
     seating.add_self();
@@ -23,7 +26,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 #[derive(Debug, PartialEq)]
-struct Relationship {
+pub struct Relationship {
     person: String,
     neighbor: String,
     happiness: i32,
@@ -165,7 +168,7 @@ fn parse_relationship(input: &str) -> IResult<&str, Relationship> {
     ))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Relationship>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Relationship>> {
     separated_list1(line_ending, parse_relationship)(input)
 }
 
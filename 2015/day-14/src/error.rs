@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is like `Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.`")
+    )]
+    InvalidInput { reason: String },
+}
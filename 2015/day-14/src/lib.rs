@@ -1,2 +1,28 @@
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+/// How long the reindeer race for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub race_duration: u32,
+}
+
+impl Params {
+    /// The race of the puzzle input.
+    pub const INPUT: Params = Params {
+        race_duration: 2503,
+    };
+    /// The shorter race of the puzzle's example.
+    pub const EXAMPLE: Params = Params {
+        race_duration: 1000,
+    };
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::INPUT
+    }
+}
+
+pub use error::PuzzleError;
@@ -7,19 +7,21 @@ use nom::{
     IResult,
 };
 
-pub fn process(input: &str) -> miette::Result<String> {
-    let input = input.trim();
-    let (_input, racers) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+use crate::{Params, PuzzleError};
 
-    #[cfg(not(test))]
-    let race_dur = 2503;
+pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
 
-    #[cfg(test)]
-    let race_dur = 1000;
+pub fn process_with(input: &str, params: Params) -> miette::Result<String> {
+    let input = input.trim();
+    let (_input, racers) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let result = racers
         .iter()
-        .map(|reindeer: &Reindeer| reindeer.distance_over_duration(race_dur))
+        .map(|reindeer: &Reindeer| reindeer.distance_over_duration(params.race_duration))
         .max()
         .unwrap_or(0);
 
@@ -27,7 +29,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 #[derive(Debug, PartialEq)]
-struct Reindeer {
+pub struct Reindeer {
     name: String,
     speed: u32,
     endurance: u32,
@@ -69,7 +71,7 @@ fn parse_reindeer(input: &str) -> IResult<&str, Reindeer> {
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Reindeer>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Reindeer>> {
     separated_list1(line_ending, parse_reindeer)(input)
 }
 
@@ -79,14 +81,13 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case(
-        "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+    #[case(Params::EXAMPLE, "1120")]
+    #[case(Params::INPUT, "2660")]
+    fn test_process(#[case] params: Params, #[case] expected: &str) -> miette::Result<()> {
+        let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
 Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.
-",
-        "1120"
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
+";
+        assert_eq!(expected, process_with(input, params)?);
         Ok(())
     }
 
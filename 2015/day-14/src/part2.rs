@@ -7,24 +7,26 @@ use nom::{
     IResult,
 };
 
-pub fn process(input: &str) -> miette::Result<String> {
-    let input = input.trim();
-    let (_input, racers) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+use crate::{Params, PuzzleError};
 
-    #[cfg(not(test))]
-    let race_dur = 2503;
+pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
 
-    #[cfg(test)]
-    let race_dur = 1000;
+pub fn process_with(input: &str, params: Params) -> miette::Result<String> {
+    let input = input.trim();
+    let (_input, racers) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let mut race = Race::new(racers.as_slice());
-    let result = race.run(race_dur);
+    let result = race.run(params.race_duration);
 
     Ok(result.to_string())
 }
 
 #[derive(Debug, PartialEq)]
-struct Reindeer {
+pub struct Reindeer {
     name: String,
     speed: u32,
     endurance: u32,
@@ -102,7 +104,7 @@ fn parse_reindeer(input: &str) -> IResult<&str, Reindeer> {
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Reindeer>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Reindeer>> {
     separated_list1(line_ending, parse_reindeer)(input)
 }
 
@@ -119,7 +121,7 @@ Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.
         "689"
     )]
     fn test_process(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
+        assert_eq!(expected, process_with(input, Params::EXAMPLE)?);
         Ok(())
     }
 
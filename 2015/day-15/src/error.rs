@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    InvalidInput { reason: String },
+    #[error("recipes of {count} ingredients are not supported")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the solver tries every recipe of 2, 3 or 4 ingredients")
+    )]
+    UnsupportedIngredients { count: usize },
+}
@@ -7,16 +7,20 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, ingredients) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, ingredients) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
-    let result = find_best_score(&ingredients);
+    let result = find_best_score(&ingredients)?;
 
     Ok(result.to_string())
 }
 
-fn find_best_score(ingredients: &[Ingredient]) -> i32 {
+fn find_best_score(ingredients: &[Ingredient]) -> Result<i32, PuzzleError> {
     let mut max_score = 0;
 
     match ingredients.len() {
@@ -49,10 +53,10 @@ fn find_best_score(ingredients: &[Ingredient]) -> i32 {
                 }
             }
         }
-        _ => panic!("Unexpected number of ingredients"),
+        count => return Err(PuzzleError::UnsupportedIngredients { count }),
     }
 
-    max_score
+    Ok(max_score)
 }
 
 fn calculate_score(ingredients: &[Ingredient], quantities: &[i32]) -> i32 {
@@ -69,7 +73,7 @@ fn calculate_score(ingredients: &[Ingredient], quantities: &[i32]) -> i32 {
 }
 
 #[derive(Debug, PartialEq)]
-struct Ingredient {
+pub struct Ingredient {
     name: String,
     capacity: i32,
     durability: i32,
@@ -110,7 +114,7 @@ fn parse_ingredient(input: &str) -> IResult<&str, Ingredient> {
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Ingredient>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Ingredient>> {
     separated_list1(line_ending, parse_ingredient)(input)
 }
 
@@ -130,6 +134,16 @@ Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
         Ok(())
     }
 
+    #[test]
+    fn test_one_ingredient() {
+        let (_, ingredients) =
+            parse("Sprinkles: capacity 2, durability 0, flavor -2, texture 0, calories 3").unwrap();
+        assert_eq!(
+            Err(PuzzleError::UnsupportedIngredients { count: 1 }),
+            find_best_score(&ingredients)
+        );
+    }
+
     #[rstest]
     #[case(
         "Sprinkles: capacity 2, durability 0, flavor -2, texture 0, calories 3",
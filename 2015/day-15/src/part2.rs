@@ -7,11 +7,15 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, ingredients) = parse(input).map_err(|e| miette::miette!("Parsing failed {e}"))?;
+    let (_input, ingredients) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
-    let result = find_best_score_with_calories(&ingredients);
+    let result = find_best_score_with_calories(&ingredients)?;
 
     Ok(result.to_string())
 }
@@ -24,7 +28,7 @@ fn calculate_total_calories(ingredients: &[Ingredient], quantities: &[i32]) -> i
         .sum()
 }
 
-fn find_best_score_with_calories(ingredients: &[Ingredient]) -> i32 {
+fn find_best_score_with_calories(ingredients: &[Ingredient]) -> Result<i32, PuzzleError> {
     let mut max_score = 0;
 
     match ingredients.len() {
@@ -63,10 +67,10 @@ fn find_best_score_with_calories(ingredients: &[Ingredient]) -> i32 {
                 }
             }
         }
-        _ => panic!("Unexpected number of ingredients"),
+        count => return Err(PuzzleError::UnsupportedIngredients { count }),
     }
 
-    max_score
+    Ok(max_score)
 }
 
 fn calculate_score(ingredients: &[Ingredient], quantities: &[i32]) -> i32 {
@@ -83,7 +87,7 @@ fn calculate_score(ingredients: &[Ingredient], quantities: &[i32]) -> i32 {
 }
 
 #[derive(Debug, PartialEq)]
-struct Ingredient {
+pub struct Ingredient {
     name: String,
     capacity: i32,
     durability: i32,
@@ -128,7 +132,7 @@ fn parse_ingredient(input: &str) -> IResult<&str, Ingredient> {
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Ingredient>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Ingredient>> {
     separated_list1(line_ending, parse_ingredient)(input)
 }
 
@@ -5,12 +5,17 @@ pub fn process(_input: &str) -> miette::Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("TODO: Build Test");
-        let input = "";
-        assert_eq!("", process(input)?);
+    // Fixtures come from `just examples day-NN`, check the
+    // answer against the puzzle text before trusting it.
+    #[rstest]
+    #[case(
+        include_str!("../fixtures/example-1.txt"),
+        include_str!("../fixtures/part2-answer.txt")
+    )]
+    fn test_process(#[case] input: &str, #[case] result: &str) -> miette::Result<()> {
+        assert_eq!(result, process(input)?);
         Ok(())
     }
 }
@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("line {line} is not a pair of location ids: `{text}`")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line holds two whole numbers separated by whitespace")
+    )]
+    InvalidLine { line: usize, text: String },
+}
@@ -1,12 +1,7 @@
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut left: Vec<i32> = vec![];
-    let mut right: Vec<i32> = vec![];
+use crate::PuzzleError;
 
-    for line in input.lines() {
-        let mut items = line.split_whitespace();
-        left.push(items.next().unwrap().parse::<i32>().unwrap());
-        right.push(items.next().unwrap().parse::<i32>().unwrap());
-    }
+pub fn process(input: &str) -> miette::Result<String> {
+    let (mut left, mut right) = parse(input)?;
 
     left.sort();
     right.sort();
@@ -18,6 +13,26 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+pub fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), PuzzleError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let invalid = || PuzzleError::InvalidLine {
+                line: i + 1,
+                text: line.to_string(),
+            };
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [left, right] => Ok((
+                    left.parse::<i32>().map_err(|_| invalid())?,
+                    right.parse::<i32>().map_err(|_| invalid())?,
+                )),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +48,19 @@ mod tests {
         assert_eq!("11", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_line() {
+        assert_eq!(
+            Err(PuzzleError::InvalidLine {
+                line: 2,
+                text: "4   x".to_string()
+            }),
+            parse("3   4\n4   x\n2   5")
+        );
+        assert!(matches!(
+            parse("3   4   5"),
+            Err(PuzzleError::InvalidLine { line: 1, .. })
+        ));
+    }
 }
@@ -1,12 +1,7 @@
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut left = vec![];
-    let mut right = vec![];
+use crate::PuzzleError;
 
-    for line in input.lines() {
-        let mut items = line.split_whitespace();
-        left.push(items.next().unwrap().parse::<usize>().unwrap());
-        right.push(items.next().unwrap().parse::<usize>().unwrap());
-    }
+pub fn process(input: &str) -> miette::Result<String> {
+    let (left, right) = parse(input)?;
 
     let result: usize = left
         .iter()
@@ -16,6 +11,26 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+pub fn parse(input: &str) -> Result<(Vec<usize>, Vec<usize>), PuzzleError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let invalid = || PuzzleError::InvalidLine {
+                line: i + 1,
+                text: line.to_string(),
+            };
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [left, right] => Ok((
+                    left.parse::<usize>().map_err(|_| invalid())?,
+                    right.parse::<usize>().map_err(|_| invalid())?,
+                )),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,14 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(
+        "line {line} is not a report of levels: `{text}`"
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line holds whole numbers separated by whitespace")
+    )]
+    InvalidReport { line: usize, text: String },
+}
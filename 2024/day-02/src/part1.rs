@@ -1,5 +1,9 @@
+use std::cmp::Ordering;
+
 use itertools::Itertools;
 
+use crate::PuzzleError;
+
 type Report = Vec<i32>;
 
 enum Direction {
@@ -8,7 +12,7 @@ enum Direction {
 }
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let reports = parse_reports(input);
+    let reports = parse_reports(input)?;
 
     let result = reports
         .iter()
@@ -18,69 +22,43 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-fn parse_reports(input: &str) -> Vec<Report> {
+pub fn parse_reports(input: &str) -> Result<Vec<Report>, PuzzleError> {
     input
         .lines()
-        .map(|x| {
-            x.split_whitespace()
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(i, line)| {
+            line.split_whitespace()
+                .map(|x| x.parse::<i32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| PuzzleError::InvalidReport {
+                    line: i + 1,
+                    text: line.to_string(),
+                })
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-fn check_safety(input: &Report) -> Result<(), String> {
+pub fn check_safety(input: &Report) -> Result<(), String> {
     let mut direction: Option<Direction> = None;
 
     for (a, b) in input.iter().tuple_windows() {
-        let diff = a - b;
-        match diff.signum() {
-            -1 => match direction {
-                Some(Direction::Increasing) => {
-                    return Err(format!("{}, {} switched to increasing", a, b));
-                }
-                Some(Direction::Decreasing) => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        continue;
-                    }
-                }
-                None => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        direction = Some(Direction::Decreasing);
-                        continue;
-                    }
-                }
-            },
-            1 => match direction {
-                Some(Direction::Increasing) => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        continue;
-                    }
-                }
-                Some(Direction::Decreasing) => {
-                    return Err(format!("{}, {} switched to decreasing", a, b));
-                }
-                None => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        direction = Some(Direction::Increasing);
-                        continue;
-                    }
-                }
-            },
-            0 => {
-                return Err(format!("{}, {} diff was 0", a, b));
+        let diff = a.abs_diff(*b);
+        if diff == 0 {
+            return Err(format!("{}, {} diff was 0", a, b));
+        }
+        match (a.cmp(b), &direction) {
+            (Ordering::Less, Some(Direction::Decreasing)) => {
+                return Err(format!("{}, {} switched to increasing", a, b));
+            }
+            (Ordering::Greater, Some(Direction::Increasing)) => {
+                return Err(format!("{}, {} switched to decreasing", a, b));
             }
-            _ => {
-                panic!("Should never have a non -1, 1, 0 number");
+            _ if !(1..=3).contains(&diff) => {
+                return Err(format!("{}, {} diff value is {}", a, b, diff));
             }
+            (Ordering::Less, None) => direction = Some(Direction::Increasing),
+            (Ordering::Greater, None) => direction = Some(Direction::Decreasing),
+            _ => {}
         }
     }
 
@@ -102,4 +80,15 @@ mod tests {
         assert_eq!("2", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_report() {
+        assert_eq!(
+            Err(PuzzleError::InvalidReport {
+                line: 2,
+                text: "1 2 x".to_string()
+            }),
+            parse_reports("7 6 4\n1 2 x")
+        );
+    }
 }
@@ -1,14 +1,7 @@
-use itertools::Itertools;
-
-type Report = Vec<i32>;
-
-enum Direction {
-    Increasing,
-    Decreasing,
-}
+use crate::part1::{check_safety, parse_reports};
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let reports = parse_reports(input);
+    let reports = parse_reports(input)?;
 
     let result = reports
         .iter()
@@ -33,75 +26,6 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-fn parse_reports(input: &str) -> Vec<Report> {
-    input
-        .lines()
-        .map(|x| {
-            x.split_whitespace()
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
-}
-
-fn check_safety(input: &Report) -> Result<(), String> {
-    let mut direction: Option<Direction> = None;
-
-    for (a, b) in input.iter().tuple_windows() {
-        let diff = a - b;
-        match diff.signum() {
-            -1 => match direction {
-                Some(Direction::Increasing) => {
-                    return Err(format!("{}, {} switched to increasing", a, b));
-                }
-                Some(Direction::Decreasing) => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        continue;
-                    }
-                }
-                None => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        direction = Some(Direction::Decreasing);
-                        continue;
-                    }
-                }
-            },
-            1 => match direction {
-                Some(Direction::Increasing) => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        continue;
-                    }
-                }
-                Some(Direction::Decreasing) => {
-                    return Err(format!("{}, {} switched to decreasing", a, b));
-                }
-                None => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!("{}, {} diff value is {}", a, b, diff.abs()));
-                    } else {
-                        direction = Some(Direction::Increasing);
-                        continue;
-                    }
-                }
-            },
-            0 => {
-                return Err(format!("{}, {} diff was 0", a, b));
-            }
-            _ => {
-                panic!("Should never have a non -1, 1, 0 number");
-            }
-        }
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
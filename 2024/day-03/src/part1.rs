@@ -1,4 +1,3 @@
-use miette::miette;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, anychar},
@@ -7,8 +6,12 @@ use nom::{
     IResult, Parser,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, instructions) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
+    let (_input, instructions) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
     let result: u32 = instructions
         .iter()
         .map(|ins| match ins {
@@ -19,7 +22,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Mul(u32, u32),
 }
 
@@ -33,7 +36,7 @@ fn instruction(input: &str) -> IResult<&str, Instruction> {
     Ok((input, Instruction::Mul(pair.0, pair.1)))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
     many1(many_till(anychar, instruction).map(|(_discard, ins)| ins))(input)
 }
 
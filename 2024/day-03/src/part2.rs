@@ -1,4 +1,3 @@
-use miette::miette;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -9,8 +8,12 @@ use nom::{
     IResult, Parser,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, instructions) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
+    let (_input, instructions) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let (_, result) = instructions
         .iter()
@@ -35,7 +38,7 @@ enum ShoudProcess {
 }
 
 #[derive(Debug, Clone)]
-enum Instruction {
+pub enum Instruction {
     Mul(u32, u32),
     Do,
     Dont,
@@ -59,7 +62,7 @@ fn instruction(input: &str) -> IResult<&str, Instruction> {
     ))(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
     many1(many_till(anychar, instruction).map(|(_discard, ins)| ins))(input)
 }
 
@@ -0,0 +1,10 @@
+use grid::GridError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Map(#[from] GridError),
+}
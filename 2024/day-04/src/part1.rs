@@ -1,6 +1,7 @@
-use std::collections::HashMap;
-
 use glam::IVec2;
+use grid::Grid;
+
+use crate::PuzzleError;
 
 const DIRECTIONS: [[IVec2; 3]; 8] = [
     [IVec2::new(0, -1), IVec2::new(0, -2), IVec2::new(0, -3)], // North
@@ -14,15 +15,7 @@ const DIRECTIONS: [[IVec2; 3]; 8] = [
 ];
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let positions = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, value)| (IVec2::new(x as i32, y as i32), value))
-        })
-        .collect::<HashMap<IVec2, char>>();
+    let positions = parse(input.trim())?;
 
     let mas = ['M', 'A', 'S'];
     let result: usize = positions
@@ -34,7 +27,7 @@ pub fn process(input: &str) -> miette::Result<String> {
                 .map(|potential_mas_pos| {
                     potential_mas_pos
                         .iter()
-                        .map(|offset| positions.get(&(position + offset)))
+                        .map(|offset| positions.get(position + offset))
                         .enumerate()
                         .all(|(index, value)| mas.get(index) == value)
                 })
@@ -48,6 +41,11 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+/// The word search, letter by letter.
+pub fn parse(input: &str) -> Result<Grid<char>, PuzzleError> {
+    Ok(Grid::parse(input, |c| c)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
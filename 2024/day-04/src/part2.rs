@@ -1,7 +1,7 @@
-use std::collections::HashMap;
-
 use glam::IVec2;
 
+use crate::part1::parse;
+
 const DIRECTIONS: [[IVec2; 2]; 4] = [
     [IVec2::new(-1, -1), IVec2::new(1, 1)],
     [IVec2::new(-1, 1), IVec2::new(1, -1)],
@@ -10,15 +10,7 @@ const DIRECTIONS: [[IVec2; 2]; 4] = [
 ];
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let positions = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, value)| (IVec2::new(x as i32, y as i32), value))
-        })
-        .collect::<HashMap<IVec2, char>>();
+    let positions = parse(input.trim())?;
 
     let mas = ['M', 'S'];
     let result: usize = positions
@@ -30,7 +22,7 @@ pub fn process(input: &str) -> miette::Result<String> {
                 .map(|possible_ms_pos| {
                     possible_ms_pos
                         .iter()
-                        .map(|pos| positions.get(&(*position + pos)))
+                        .map(|pos| positions.get(*position + pos))
                         .enumerate()
                         .all(|(index, value)| mas.get(index) == value)
                 })
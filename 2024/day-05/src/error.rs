@@ -0,0 +1,14 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help(
+            "the rules like `47|53` come first, then a blank line and the updates like `75,47,61`"
+        )
+    )]
+    InvalidInput { reason: String },
+}
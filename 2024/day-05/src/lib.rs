@@ -1,2 +1,5 @@
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
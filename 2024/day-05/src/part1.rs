@@ -1,4 +1,3 @@
-use miette::miette;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -8,8 +7,12 @@ use nom::{
 };
 use std::collections::HashMap;
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
-    let (input, (rules, updates)) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
+    let (input, (rules, updates)) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
     let results: Vec<usize> = updates
         .iter()
@@ -72,7 +75,7 @@ fn updates(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
     separated_list1(line_ending, separated_list1(tag(","), complete::u32))(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (HashMap<u32, Vec<u32>>, Vec<Vec<u32>>)> {
+pub fn parse(input: &str) -> IResult<&str, (HashMap<u32, Vec<u32>>, Vec<Vec<u32>>)> {
     let (input, parsed_rules) = terminated(rules, line_ending)(input)?;
     let (input, parsed_updates) = updates(input)?;
     Ok((input, (parsed_rules, parsed_updates)))
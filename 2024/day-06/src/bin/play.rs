@@ -0,0 +1,9 @@
+use day_06::{guard::Guard, part1::parse};
+
+/// Shows the guard's patrol step by step.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let guard = Guard::new(parse(file.trim())?)?;
+    simulation::play(guard)?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use grid::GridError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Map(#[from] GridError),
+    #[error("the map has no guard")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the guard starts on the `^`")
+    )]
+    NoGuard,
+    #[error("the guard walks in a loop and never leaves the map")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    GuardLoops,
+}
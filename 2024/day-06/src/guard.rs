@@ -0,0 +1,174 @@
+//! The guard's patrol, one move or turn at a time.
+
+use std::collections::HashSet;
+
+use glam::IVec2;
+use grid::Grid;
+use simulation::Simulation;
+
+use crate::PuzzleError;
+
+/// The lab with the guard walking through it, turning right
+/// in front of every obstacle until leaving the map or
+/// coming back to where it has been, facing the same way.
+#[derive(Debug, Clone)]
+pub struct Guard {
+    map: Grid<char>,
+    position: IVec2,
+    direction: IVec2,
+    visited: HashSet<IVec2>,
+    /// Every position and direction the guard has been in.
+    states: HashSet<(IVec2, IVec2)>,
+    left: bool,
+    looped: bool,
+}
+
+impl Guard {
+    /// Starts the guard at the map's `^`, facing north.
+    pub fn new(
+        mut map: Grid<char>,
+    ) -> Result<Self, PuzzleError> {
+        let position = map
+            .find(|c| c == &'^')
+            .ok_or(PuzzleError::NoGuard)?;
+        map[position] = '.';
+        Ok(Self {
+            map,
+            position,
+            direction: IVec2::NEG_Y,
+            visited: HashSet::from([position]),
+            states: HashSet::from([(
+                position,
+                IVec2::NEG_Y,
+            )]),
+            left: false,
+            looped: false,
+        })
+    }
+
+    /// Positions the guard has been on, including the start.
+    pub fn visited(&self) -> &HashSet<IVec2> {
+        &self.visited
+    }
+
+    /// Whether the guard came back to a position it had
+    /// been in facing the same way, walking in circles
+    /// from then on.
+    pub fn loops(&self) -> bool {
+        self.looped
+    }
+}
+
+impl Simulation for Guard {
+    fn step(&mut self) -> bool {
+        if self.left || self.looped {
+            return false;
+        }
+        let next = self.position + self.direction;
+        match self.map.get(next) {
+            // Right of north is east, with y growing south.
+            Some('#') => {
+                self.direction = self.direction.perp()
+            }
+            Some(_) => {
+                self.position = next;
+                self.visited.insert(next);
+            }
+            None => {
+                self.left = true;
+                return true;
+            }
+        }
+        self.looped = !self
+            .states
+            .insert((self.position, self.direction));
+        true
+    }
+
+    fn render(&self) -> Grid<char> {
+        let guard = match self.direction.to_array() {
+            [0, -1] => '^',
+            [1, 0] => '>',
+            [0, 1] => 'v',
+            _ => '<',
+        };
+        Grid::from_fn(
+            self.map.width(),
+            self.map.height(),
+            |position| {
+                if position == self.position && !self.left {
+                    guard
+                } else if self.visited.contains(&position) {
+                    'X'
+                } else {
+                    self.map[position]
+                }
+            },
+        )
+    }
+
+    fn status(&self) -> String {
+        let looping =
+            if self.looped { ", looping" } else { "" };
+        format!(
+            "{} positions visited{looping}",
+            self.visited.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::part1::parse;
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let mut guard =
+            Guard::new(parse("..#.\n....\n..^.")?)?;
+        guard.step();
+        guard.step();
+        assert_eq!(
+            "..#.\n..>.\n..X.",
+            guard.render().to_string()
+        );
+
+        assert_eq!(2, guard.run());
+        assert_eq!(
+            "..#.\n..XX\n..X.",
+            guard.render().to_string()
+        );
+        assert_eq!("3 positions visited", guard.status());
+        Ok(())
+    }
+
+    #[test]
+    fn test_loops() -> miette::Result<()> {
+        let mut guard =
+            Guard::new(parse(".#..\n...#\n#^..\n..#.")?)?;
+        assert_eq!(8, guard.run());
+        assert!(guard.loops());
+        assert_eq!(
+            "4 positions visited, looping",
+            guard.status()
+        );
+
+        // Walled in, the guard only ever turns.
+        let mut guard =
+            Guard::new(parse(".#.\n#^#\n.#.")?)?;
+        assert_eq!(4, guard.run());
+        assert!(guard.loops());
+        assert_eq!(1, guard.visited().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_guard() -> miette::Result<()> {
+        assert_eq!(
+            Some(PuzzleError::NoGuard),
+            Guard::new(parse("..#.\n....")?).err()
+        );
+        Ok(())
+    }
+}
@@ -1,2 +1,6 @@
+pub mod error;
+pub mod guard;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
@@ -0,0 +1,15 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a test value and its numbers, like `190: 10 19`")
+    )]
+    InvalidInput { reason: String },
+    #[error("the total calibration result does not fit in 64 bits")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    Overflow,
+}
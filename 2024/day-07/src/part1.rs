@@ -1,5 +1,4 @@
 use itertools::Itertools;
-use miette::miette;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending, space1},
@@ -8,39 +7,70 @@ use nom::{
     IResult,
 };
 
-const OPERATORS: [char; 2] = ['*', '+'];
+use crate::PuzzleError;
+
+const OPERATORS: [Operator; 2] = [Operator::Multiply, Operator::Add];
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, equations) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
+    let (_input, equations) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
+
+    Ok(calibrate(&equations, &OPERATORS)?.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+}
+
+impl Operator {
+    /// `a` and `b` combined, `None` when the result does not
+    /// fit in a `u64`, which no test value can match.
+    fn apply(self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Multiply => a.checked_mul(b),
+            Operator::Concatenate => {
+                let digits = b.checked_ilog10().unwrap_or(0) + 1;
+                a.checked_mul(10u64.checked_pow(digits)?)?.checked_add(b)
+            }
+        }
+    }
+}
 
-    let result: u64 = equations
+/// Sum of the test values that some choice of `operators`
+/// between the numbers can produce.
+pub fn calibrate(
+    equations: &[(u64, Vec<u64>)],
+    operators: &[Operator],
+) -> Result<u64, PuzzleError> {
+    equations
         .iter()
-        .filter_map(|(test, numbers)| {
-            let operator_count = numbers.len() - 1;
-            (0..operator_count)
-                .map(|_| OPERATORS)
+        .filter(|(test, numbers)| {
+            let Some((first, rest)) = numbers.split_first() else {
+                return false;
+            };
+            (0..rest.len())
+                .map(|_| operators.iter().copied())
                 .multi_cartesian_product()
                 .any(|seq| {
-                    let mut s = seq.iter();
-                    let result = numbers
+                    let result = rest
                         .iter()
-                        .copied()
-                        .reduce(|acc, next_num| match s.next().unwrap() {
-                            '*' => acc * next_num,
-                            '+' => acc + next_num,
-                            _ => panic!("invalid operator"),
-                        })
-                        .unwrap();
-                    *test == result
+                        .zip(seq)
+                        .try_fold(*first, |acc, (&next_num, operator)| {
+                            operator.apply(acc, next_num)
+                        });
+                    result == Some(*test)
                 })
-                .then_some(test)
         })
-        .sum();
-
-    Ok(result.to_string())
+        .try_fold(0u64, |sum, (test, _)| sum.checked_add(*test))
+        .ok_or(PuzzleError::Overflow)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
     separated_list1(
         line_ending,
         separated_pair(
@@ -69,4 +99,14 @@ mod tests {
         assert_eq!("3749", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Some(1234), Operator::Concatenate.apply(12, 34));
+        assert_eq!(None, Operator::Multiply.apply(u64::MAX, 2));
+        assert_eq!(
+            Err(PuzzleError::Overflow),
+            calibrate(&[(u64::MAX, vec![u64::MAX]), (1, vec![1])], &OPERATORS)
+        );
+    }
 }
@@ -1,5 +1,3 @@
-use itertools::Itertools;
-use miette::miette;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending, space1},
@@ -8,40 +6,22 @@ use nom::{
     IResult,
 };
 
-const OPERATORS: [char; 3] = ['*', '+', '|'];
+use crate::{
+    part1::{calibrate, Operator},
+    PuzzleError,
+};
 
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, equations) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
+const OPERATORS: [Operator; 3] = [Operator::Multiply, Operator::Add, Operator::Concatenate];
 
-    let result: u64 = equations
-        .iter()
-        .filter_map(|(test, numbers)| {
-            let operator_count = numbers.len() - 1;
-            (0..operator_count)
-                .map(|_| OPERATORS)
-                .multi_cartesian_product()
-                .any(|seq| {
-                    let mut s = seq.iter();
-                    let result = numbers
-                        .iter()
-                        .copied()
-                        .reduce(|acc, next_num| match s.next().unwrap() {
-                            '*' => acc * next_num,
-                            '+' => acc + next_num,
-                            '|' => format!("{}{}", acc, next_num).parse::<u64>().unwrap(),
-                            _ => panic!("invalid operator"),
-                        })
-                        .unwrap();
-                    *test == result
-                })
-                .then_some(test)
-        })
-        .sum();
+pub fn process(input: &str) -> miette::Result<String> {
+    let (_input, equations) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
-    Ok(result.to_string())
+    Ok(calibrate(&equations, &OPERATORS)?.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
     separated_list1(
         line_ending,
         separated_pair(
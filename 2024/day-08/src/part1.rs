@@ -1,23 +1,16 @@
-use glam::IVec2;
+use grid::Grid;
 use itertools::Itertools;
-use miette::miette;
-use nom::{
-    bytes::complete::take_till, character::complete::satisfy, multi::many1, sequence::preceded,
-    AsChar, IResult,
-};
-use nom_locate::{position, LocatedSpan};
 
-pub type Span<'a> = LocatedSpan<&'a str>;
+use crate::PuzzleError;
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
+    let map = parse(input.trim())?;
 
-    let y_bound = 0..height as i32;
-    let x_bound = 0..width as i32;
-
-    let (input, mut result) = parse(Span::new(input)).map_err(|e| miette!("parse failed {}", e))?;
-    result.sort_by(|a, b| a.1.cmp(&b.1));
+    let mut result = map
+        .iter()
+        .filter(|(_, c)| c.is_alphanumeric())
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| a.1.cmp(b.1));
     let results = result
         .chunk_by(|a, b| a.1 == b.1)
         .flat_map(|chunk| {
@@ -28,23 +21,16 @@ pub fn process(input: &str) -> miette::Result<String> {
                     let diff = sats[0].0 - sats[1].0;
                     [sats[0].0 + diff, sats[1].0 - diff]
                 })
-                .filter(|pos| x_bound.contains(&pos.x) && y_bound.contains(&pos.y))
+                .filter(|pos| map.in_bounds(*pos))
         })
         .unique()
         .count();
     Ok(results.to_string())
 }
 
-fn alphanum_pos(input: Span) -> IResult<Span, (IVec2, char)> {
-    let (input, pos) = position(input)?;
-    let x = pos.get_column() as i32 - 1;
-    let y = pos.location_line() as i32 - 1;
-    let (input, c) = satisfy(|c| c.is_alphanum())(input)?;
-    Ok((input, (IVec2::new(x, y), c)))
-}
-
-fn parse(input: Span) -> IResult<Span, Vec<(IVec2, char)>> {
-    many1(preceded(take_till(|c: char| c.is_alphanum()), alphanum_pos))(input)
+/// The map of antennas, `.` where there is none.
+pub fn parse(input: &str) -> Result<Grid<char>, PuzzleError> {
+    Ok(Grid::parse(input, |c| c)?)
 }
 
 #[cfg(test)]
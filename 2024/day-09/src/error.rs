@@ -0,0 +1,12 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("`{character}` at position {position} is not a digit")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the disk map is a single line of digits")
+    )]
+    InvalidDigit { position: usize, character: char },
+}
@@ -1,13 +1,13 @@
 use std::collections::VecDeque;
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let nums: Vec<_> = input
-        .chars()
+    let nums: Vec<_> = parse(input)?
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| {
-            let count = c.to_digit(10).unwrap() as usize;
-
+        .map(|(i, count)| {
             if i % 2 != 0 {
                 (count, None)
             } else {
@@ -44,6 +44,23 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+/// The lengths of the files and free spaces in turn.
+pub fn parse(input: &str) -> Result<Vec<usize>, PuzzleError> {
+    input
+        .chars()
+        .enumerate()
+        .map(|(position, character)| {
+            character
+                .to_digit(10)
+                .map(|count| count as usize)
+                .ok_or(PuzzleError::InvalidDigit {
+                    position: position + 1,
+                    character,
+                })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
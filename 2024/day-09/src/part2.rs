@@ -1,13 +1,13 @@
 use std::collections::VecDeque;
 
+use crate::part1::parse;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let mut files: Vec<_> = input
-        .chars()
+    let mut files: Vec<_> = parse(input)?
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| {
-            let count = c.to_digit(10).unwrap() as usize;
-
+        .map(|(i, count)| {
             if i % 2 != 0 {
                 (count, None, false)
             } else {
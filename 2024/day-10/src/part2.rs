@@ -1,27 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use glam::IVec2;
-use miette::miette;
-use nom::{
-    character::complete::{line_ending, satisfy},
-    multi::{many1, separated_list1},
-    IResult,
-};
-use nom_locate::{position, LocatedSpan};
+use grid::{Grid, ORTHOGONAL as DIRECTIONS};
 use pathfinding::prelude::count_paths;
 
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+use crate::part1::parse;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let data = input.trim();
-    let (_input, map) = parse(Span::new(data)).map_err(|e| miette!("parse failed {}", e))?;
+    let map = parse(data)?;
 
     let counts: usize = map
         .iter()
         .filter(|(_, height)| height == &&0)
-        .map(|(pos, _)| (pos, search_trail(&map, pos)))
+        .map(|(pos, _)| (pos, search_trail(&map, &pos)))
         .map(|(starting_position, ending_positions)| {
-            rate_trail(&map, starting_position, &ending_positions)
+            rate_trail(&map, &starting_position, &ending_positions)
         })
         .sum();
 
@@ -29,7 +23,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 }
 
 fn rate_trail(
-    map: &HashMap<IVec2, u32>,
+    map: &Grid<u32>,
     starting_position: &IVec2,
     ending_positions: &[IVec2],
 ) -> usize {
@@ -44,8 +38,8 @@ fn rate_trail(
                         .zip(std::iter::repeat(*pos))
                         .map(|(dir, location)| (dir + location, location))
                         .filter(|(new_location, location)| {
-                            map.get(new_location).is_some_and(|h| {
-                                let current_height = map.get(location).unwrap();
+                            map.get(*new_location).is_some_and(|h| {
+                                let current_height = map[*location];
                                 *h == current_height + 1
                             })
                         })
@@ -57,7 +51,7 @@ fn rate_trail(
         .sum::<usize>()
 }
 
-fn search_trail(map: &HashMap<IVec2, u32>, position: &IVec2) -> Vec<IVec2> {
+fn search_trail(map: &Grid<u32>, position: &IVec2) -> Vec<IVec2> {
     let mut visited: HashSet<IVec2> = HashSet::from([]);
     let mut new_locations: HashSet<IVec2> = HashSet::from([*position]);
     loop {
@@ -75,8 +69,8 @@ fn search_trail(map: &HashMap<IVec2, u32>, position: &IVec2) -> Vec<IVec2> {
                     .filter(|(new_location, location)| {
                         !visited.contains(new_location)
                             && !new_locations.contains(new_location)
-                            && map.get(new_location).is_some_and(|h| {
-                                let current_height = map.get(location).unwrap();
+                            && map.get(*new_location).is_some_and(|h| {
+                                let current_height = map[**location];
                                 *h == current_height + 1
                             })
                     })
@@ -93,33 +87,11 @@ fn search_trail(map: &HashMap<IVec2, u32>, position: &IVec2) -> Vec<IVec2> {
 
     visited
         .iter()
-        .filter(|pos| map.get(pos).unwrap() == &9)
+        .filter(|pos| map[**pos] == 9)
         .cloned()
         .collect()
 }
 
-pub type Span<'a> = LocatedSpan<&'a str>;
-fn alphanum_pos(input: Span) -> IResult<Span, (IVec2, u32)> {
-    let (input, pos) = position(input)?;
-    let x = pos.get_column() as i32 - 1;
-    let y = pos.location_line() as i32 - 1;
-    let (input, c) = satisfy(|c| c.is_numeric())(input)?;
-
-    Ok((input, (IVec2::new(x, y), c.to_digit(10).unwrap())))
-}
-
-fn parse(input: Span) -> IResult<Span, HashMap<IVec2, u32>> {
-    let (input, lines) = separated_list1(line_ending, many1(alphanum_pos))(input)?;
-
-    let hashmap = lines
-        .iter()
-        .flatten()
-        .copied()
-        .collect::<HashMap<IVec2, u32>>();
-
-    Ok((input, hashmap))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
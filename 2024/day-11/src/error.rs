@@ -0,0 +1,17 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("`{text}` is not a stone's number")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the stones are whole numbers separated by whitespace")
+    )]
+    InvalidStone { text: String },
+    #[error(
+        "stone {stone} times 2024 does not fit in 64 bits"
+    )]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    Overflow { stone: u64 },
+}
@@ -1,29 +1,50 @@
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let data = input.trim();
 
-    let nums = data
+    let mut nums = parse(data)?;
+    for _ in 0..25 {
+        nums = blink(&nums)?;
+    }
+
+    Ok(nums.len().to_string())
+}
+
+/// The stones after blinking once.
+fn blink(nums: &[u64]) -> Result<Vec<u64>, PuzzleError> {
+    let mut next = Vec::with_capacity(nums.len() * 2);
+    for &num in nums {
+        match num {
+            0 => next.push(1),
+            n if (n.checked_ilog10().unwrap_or(0) + 1) % 2 == 0 => {
+                let (a, b) = split_num_digits(n);
+                next.extend([a, b]);
+            }
+            _ => next.push(times_2024(num)?),
+        }
+    }
+    Ok(next)
+}
+
+/// The stones engraved with their numbers.
+pub fn parse(input: &str) -> Result<Vec<u64>, PuzzleError> {
+    input
         .split_ascii_whitespace()
-        .map(|n| n.parse::<u64>().expect("numbers to be valid in AoC"))
-        .collect::<Vec<u64>>();
-
-    let mut all_iterations = std::iter::successors(Some(nums), |nums| {
-        let iter_nums: Vec<u64> = nums
-            .iter()
-            .flat_map(|num| match num {
-                0 => vec![1],
-                n if (n.checked_ilog10().unwrap_or(0) + 1) % 2 == 0 => {
-                    let (a, b) = split_num_digits(*n);
-                    vec![a, b]
-                }
-                _ => vec![num * 2024],
+        .map(|n| {
+            n.parse::<u64>().map_err(|_| PuzzleError::InvalidStone {
+                text: n.to_string(),
             })
-            .collect();
-        Some(iter_nums)
-    });
-
-    let result = all_iterations.nth(25).unwrap().len();
+        })
+        .collect()
+}
 
-    Ok(result.to_string())
+/// A stone's number multiplied by 2024, which can grow past
+/// what 64 bits hold.
+pub fn times_2024(stone: u64) -> Result<u64, PuzzleError> {
+    stone
+        .checked_mul(2024)
+        .ok_or(PuzzleError::Overflow { stone })
 }
 
 fn split_num_digits(num: u64) -> (u64, u64) {
@@ -43,4 +64,16 @@ mod tests {
         assert_eq!("55312", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_stones() {
+        assert_eq!(
+            Err(PuzzleError::InvalidStone {
+                text: "-1".to_string()
+            }),
+            parse("125 -1")
+        );
+        let stone = u64::MAX / 1000;
+        assert_eq!(Err(PuzzleError::Overflow { stone }), blink(&[stone]));
+    }
 }
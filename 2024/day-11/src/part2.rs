@@ -1,49 +1,55 @@
 use std::collections::HashMap;
 
+use crate::{
+    part1::{parse, times_2024},
+    PuzzleError,
+};
+
 pub fn process(input: &str) -> miette::Result<String> {
     let data = input.trim();
 
-    let nums = data
-        .split_whitespace()
-        .map(|n| n.parse::<usize>().expect("numbers to be valid in AoC"))
-        .collect::<Vec<usize>>();
+    let nums = parse(data)?;
 
     let mut mem = HashMap::new();
 
-    let result: usize = nums
-        .iter()
-        .map(|&num| get_stone_count(num, 75, &mut mem))
-        .sum();
+    let mut result: usize = 0;
+    for num in nums {
+        result += get_stone_count(num, 75, &mut mem)?;
+    }
 
     Ok(result.to_string())
 }
 
-fn split_num_digits(num: usize) -> (usize, usize) {
+fn split_num_digits(num: u64) -> (u64, u64) {
     let len = (num as f64).log10().floor() as u32 + 1;
     let divisor = 10u64.pow(len / 2);
 
-    (num / divisor as usize, num % divisor as usize)
+    (num / divisor, num % divisor)
 }
 
-fn get_stone_count(num: usize, depth: usize, mem: &mut HashMap<(usize, usize), usize>) -> usize {
+fn get_stone_count(
+    num: u64,
+    depth: usize,
+    mem: &mut HashMap<(u64, usize), usize>,
+) -> Result<usize, PuzzleError> {
     let key = (num, depth);
 
-    if mem.contains_key(&key) {
-        *mem.get(&key).unwrap()
+    if let Some(&count) = mem.get(&key) {
+        Ok(count)
     } else {
         let result = if depth == 0 {
             1
         } else if num == 0 {
-            get_stone_count(1, depth - 1, mem)
+            get_stone_count(1, depth - 1, mem)?
         } else if (num.checked_ilog10().unwrap_or(0) + 1) % 2 == 0 {
             let (a, b) = split_num_digits(num);
-            get_stone_count(a, depth - 1, mem) + get_stone_count(b, depth - 1, mem)
+            get_stone_count(a, depth - 1, mem)? + get_stone_count(b, depth - 1, mem)?
         } else {
-            get_stone_count(num * 2024, depth - 1, mem)
+            get_stone_count(times_2024(num)?, depth - 1, mem)?
         };
 
         mem.insert(key, result);
-        result
+        Ok(result)
     }
 }
 
@@ -54,7 +60,7 @@ mod tests {
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "125 17";
-        assert_eq!("55312", process(input)?);
+        assert_eq!("65601038650482", process(input)?);
         Ok(())
     }
 }
@@ -1,30 +1,12 @@
-use std::collections::HashMap;
+use glam::IVec2;
+use grid::{Grid, ORTHOGONAL};
 
-use cgmath::Vector2;
-
-type Vec2 = Vector2<isize>;
-
-fn dirs() -> Vec<Vec2> {
-    vec![
-        Vec2::new(1, 0),
-        Vec2::new(-1, 0),
-        Vec2::new(0, 1),
-        Vec2::new(0, -1),
-    ]
-}
+use crate::part1::parse;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let data = input.trim();
 
-    let plant_map = data
-        .lines()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.chars()
-                .enumerate()
-                .map(move |(x, c)| (Vec2::new(x as isize, y as isize), c))
-        })
-        .collect::<HashMap<Vec2, char>>();
+    let plant_map = parse(data)?;
 
     let groups = split_into_groups(plant_map);
 
@@ -36,11 +18,11 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-fn get_fence(group: &[Vec2]) -> usize {
-    let mut fence_parts: Vec<(Vec2, Vec2)> = group
+fn get_fence(group: &[IVec2]) -> usize {
+    let mut fence_parts: Vec<(IVec2, IVec2)> = group
         .iter()
         .flat_map(|&p1| {
-            dirs()
+            ORTHOGONAL
                 .into_iter()
                 .filter(move |dir| !group.contains(&(p1 + dir)))
                 .map(move |dir| (p1, dir))
@@ -56,8 +38,8 @@ fn get_fence(group: &[Vec2]) -> usize {
     count
 }
 
-fn reduce_fence(part: (Vec2, Vec2), fence_parts: &mut Vec<(Vec2, Vec2)>) {
-    dirs().into_iter().for_each(|dir| {
+fn reduce_fence(part: (IVec2, IVec2), fence_parts: &mut Vec<(IVec2, IVec2)>) {
+    ORTHOGONAL.into_iter().for_each(|dir| {
         let p = part.0 + dir;
         if let Some(index) = fence_parts
             .iter()
@@ -69,32 +51,36 @@ fn reduce_fence(part: (Vec2, Vec2), fence_parts: &mut Vec<(Vec2, Vec2)>) {
     });
 }
 
-fn split_into_groups(mut plant_map: HashMap<Vec2, char>) -> Vec<Vec<Vec2>> {
+/// Plants are taken out of the map as they are grouped,
+/// so every plot ends up in exactly one group.
+fn split_into_groups(mut plant_map: Grid<Option<char>>) -> Vec<Vec<IVec2>> {
     let mut groups = vec![];
 
-    while let Some((&pos, &c)) = plant_map.iter().next() {
-        let mut group = vec![];
+    for pos in plant_map.positions().collect::<Vec<_>>() {
+        if let Some(c) = plant_map[pos] {
+            let mut group = vec![];
 
-        collect_groups(pos, c, &mut plant_map, &mut group);
+            collect_groups(pos, c, &mut plant_map, &mut group);
 
-        groups.push(group);
+            groups.push(group);
+        }
     }
 
     groups
 }
 
 fn collect_groups(
-    pos: Vector2<isize>,
+    pos: IVec2,
     c: char,
-    plant_map: &mut HashMap<Vec2, char>,
-    group: &mut Vec<Vec2>,
+    plant_map: &mut Grid<Option<char>>,
+    group: &mut Vec<IVec2>,
 ) {
-    if let Some(&c1) = plant_map.get(&pos) {
-        if c1 == c {
-            plant_map.remove(&pos);
+    if let Some(plant) = plant_map.get_mut(pos) {
+        if *plant == Some(c) {
+            *plant = None;
             group.push(pos);
 
-            for dir in dirs().into_iter() {
+            for dir in ORTHOGONAL {
                 collect_groups(pos + dir, c, plant_map, group);
             }
         }
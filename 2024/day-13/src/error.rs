@@ -0,0 +1,28 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("machine {machine} has no two numbers on line `{line}`")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("a machine is its button A, button B and prize lines, like `Button A: X+94, Y+34`")
+    )]
+    InvalidMachine { machine: usize, line: String },
+    #[error(
+        "machine {machine} is missing its {missing} line"
+    )]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    MissingLine {
+        machine: usize,
+        missing: &'static str,
+    },
+    #[error("both buttons of machine {machine} move the claw in the same direction")]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help(
+            "the solver needs a single way to reach the prize, which parallel buttons do not give"
+        )
+    )]
+    ParallelButtons { machine: usize },
+}
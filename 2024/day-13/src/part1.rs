@@ -1,62 +1,87 @@
 use cgmath::Vector2;
 use regex::Regex;
 
-type Vec2 = Vector2<isize>;
+use crate::PuzzleError;
 
-struct Equation {
-    a: Vec2,
-    b: Vec2,
-    prize: Vec2,
-}
-impl Equation {
-    fn count_tokens(&self) -> Option<usize> {
-        let px = self.prize.x;
-        let py = self.prize.y;
+pub type Vec2 = Vector2<i64>;
 
-        let ax = self.a.x;
-        let ay = self.a.y;
+pub struct Equation {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub prize: Vec2,
+}
 
-        let bx = self.b.x;
-        let by = self.b.y;
+impl Equation {
+    /// Tokens to win the prize, `None` when no whole number
+    /// of presses reaches it.
+    pub fn count_tokens(&self) -> Option<i128> {
+        let [px, py] = [self.prize.x, self.prize.y].map(i128::from);
+        let [ax, ay] = [self.a.x, self.a.y].map(i128::from);
+        let [bx, by] = [self.b.x, self.b.y].map(i128::from);
 
         let a_btn_presses = (by * px - bx * py) / (ax * by - ay * bx);
         let b_btn_presses = (ay * px - ax * py) / (ay * bx - ax * by);
 
-        if px == a_btn_presses * ax + b_btn_presses * bx
+        if a_btn_presses >= 0
+            && b_btn_presses >= 0
+            && px == a_btn_presses * ax + b_btn_presses * bx
             && py == a_btn_presses * ay + b_btn_presses * by
         {
-            Some((3 * a_btn_presses + b_btn_presses) as usize)
+            Some(3 * a_btn_presses + b_btn_presses)
         } else {
             None
         }
     }
+
+    fn is_parallel(&self) -> bool {
+        i128::from(self.a.x) * i128::from(self.b.y) == i128::from(self.a.y) * i128::from(self.b.x)
+    }
 }
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let data = input.trim();
-
-    let data: Vec<_> = data.lines().collect();
-    let data: Vec<_> = data.split(|line| line.is_empty()).collect();
-
-    let data: Vec<_> = data
-        .iter()
-        .map(|lines| {
-            let mut iter = lines.iter();
-            let a = iter.next().map(|s| str_to_vec2(s)).unwrap();
-            let b = iter.next().map(|s| str_to_vec2(s)).unwrap();
-            let prize = iter.next().map(|s| str_to_vec2(s)).unwrap();
-            Equation { a, b, prize }
-        })
-        .collect();
+    let data = parse(input.trim())?;
 
-    let sum: usize = data.iter().filter_map(|e| e.count_tokens()).sum();
+    let sum: i128 = data.iter().filter_map(|e| e.count_tokens()).sum();
     Ok(sum.to_string())
 }
 
-fn str_to_vec2(s: &str) -> Vec2 {
-    let re = Regex::new(r"(\d+).*?(\d+)").unwrap();
-    let capture = re.captures_iter(s).next().unwrap();
-    Vec2::new(capture[1].parse().unwrap(), capture[2].parse().unwrap())
+/// The claw machines, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<Equation>, PuzzleError> {
+    let re = Regex::new(r"(\d+).*?(\d+)").expect("a valid regex");
+
+    let data: Vec<_> = input.lines().collect();
+    data.split(|line| line.is_empty())
+        .enumerate()
+        .map(|(i, lines)| {
+            let machine = i + 1;
+            let mut iter = lines.iter();
+            let mut line = |missing| {
+                let line = iter
+                    .next()
+                    .ok_or(PuzzleError::MissingLine { machine, missing })?;
+                re.captures(line)
+                    .and_then(|capture| {
+                        Some(Vec2::new(
+                            capture[1].parse().ok()?,
+                            capture[2].parse().ok()?,
+                        ))
+                    })
+                    .ok_or_else(|| PuzzleError::InvalidMachine {
+                        machine,
+                        line: line.to_string(),
+                    })
+            };
+            let equation = Equation {
+                a: line("button A")?,
+                b: line("button B")?,
+                prize: line("prize")?,
+            };
+            if equation.is_parallel() {
+                return Err(PuzzleError::ParallelButtons { machine });
+            }
+            Ok(equation)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -83,4 +108,26 @@ Prize: X=18641, Y=10279";
         assert_eq!("480", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_machines() {
+        assert_eq!(
+            Some(PuzzleError::ParallelButtons { machine: 1 }),
+            parse("Button A: X+2, Y+4\nButton B: X+1, Y+2\nPrize: X=8, Y=16").err()
+        );
+        assert_eq!(
+            Some(PuzzleError::MissingLine {
+                machine: 1,
+                missing: "prize"
+            }),
+            parse("Button A: X+2, Y+4\nButton B: X+3, Y+2").err()
+        );
+        assert_eq!(
+            Some(PuzzleError::InvalidMachine {
+                machine: 1,
+                line: "Prize: X=8".to_string()
+            }),
+            parse("Button A: X+2, Y+4\nButton B: X+3, Y+2\nPrize: X=8").err()
+        );
+    }
 }
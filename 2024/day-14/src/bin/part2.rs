@@ -1,10 +1,31 @@
- use day_14::part2::process;
- use miette::Context;
+use day_14::{
+    part2::{find_tree, process},
+    robots::{parse, Robots},
+    Params, PuzzleError,
+};
+use miette::Context;
+use simulation::image::{Palette, Recorder};
 
- fn main() -> miette::Result<()> {
-     let file = include_str!("../../input2.txt");
-     let result = process(file).context("process part 2")?;
-     println!("{}", result);
-     Ok(())
- }
+fn main() -> miette::Result<()> {
+    let args = aoc_input::Args::from_env(env!("CARGO_MANIFEST_DIR"))?;
+    let file = args.source.read()?;
+    let result = process(&file).context("process part 2")?;
+    println!("{}", result);
 
+    if let Some(dir) = args.frames {
+        let (_, robots) = parse(file.trim()).map_err(|e| PuzzleError::InvalidInput {
+            reason: e.to_string(),
+        })?;
+        let mut robots = Robots::new(robots, Params::INPUT.map_size)?;
+        let seconds = find_tree(&robots)?;
+
+        // Every second up to the tree, a robot four pixels
+        // wide.
+        let palette = Palette::new([40, 200, 80]).with(".", [10, 12, 30]);
+        let mut recorder = Recorder::create(&dir)?.scale(4);
+        recorder.record_simulation(&mut robots, &palette, seconds as usize)?;
+        let frames = recorder.finish()?;
+        eprintln!("wrote {frames} frames to {}", dir.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use day_14::{
+    robots::{parse, Robots},
+    Params, PuzzleError,
+};
+
+/// Shows the robots moving through the bathroom second by
+/// second.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let (_, robots) = parse(file.trim()).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
+    simulation::play(Robots::new(robots, Params::INPUT.map_size)?)?;
+    Ok(())
+}
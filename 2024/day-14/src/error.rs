@@ -0,0 +1,28 @@
+use glam::IVec2;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a robot, like `p=0,4 v=3,-3`")
+    )]
+    InvalidInput { reason: String },
+    #[error("the space is {map_size}, it needs at least one cell along each axis")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    MapSize { map_size: IVec2 },
+    #[error("the robots never cluster along {axis}, the closest phase is only {score:.1} standard deviations below the average")]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help("a picture needs most robots to come together at once, see the `play` binary")
+    )]
+    NoClustering { axis: char, score: f64 },
+    #[error("no time has the columns clustered at {x} and the rows at {y}")]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help("the width and height of the space share a factor, so not every pair of phases comes together")
+    )]
+    NoCommonTime { x: i32, y: i32 },
+}
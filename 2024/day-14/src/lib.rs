@@ -1,2 +1,35 @@
+use glam::IVec2;
+
+pub mod error;
 pub mod part1;
 pub mod part2;
+pub mod robots;
+
+pub use error::PuzzleError;
+
+/// Size of the space the robots move in and how long part 1
+/// watches them for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub map_size: IVec2,
+    pub seconds: u32,
+}
+
+impl Params {
+    /// The bathroom of the puzzle input.
+    pub const INPUT: Params = Params {
+        map_size: IVec2::new(101, 103),
+        seconds: 100,
+    };
+    /// The smaller space of the puzzle's example.
+    pub const EXAMPLE: Params = Params {
+        map_size: IVec2::new(11, 7),
+        seconds: 100,
+    };
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::INPUT
+    }
+}
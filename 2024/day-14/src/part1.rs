@@ -1,40 +1,41 @@
-use glam::IVec2;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult, Parser,
-};
+use simulation::Simulation;
 
-const MAP_SIZE: IVec2 = if cfg!(test) {
-    IVec2::new(11, 7)
-} else {
-    IVec2::new(101, 103)
+use crate::{
+    robots::{Robot, Robots},
+    Params, PuzzleError,
 };
 
+pub use crate::robots::parse;
+
 pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
+
+pub fn process_with(input: &str, params: Params) -> miette::Result<String> {
+    let Params { map_size, .. } = params;
     let input = input.trim();
-    let (_, mut robots) = parse(input).map_err(|e| miette::miette!("parse failed {}", e))?;
+    let (_, robots) = parse(input).map_err(|e| PuzzleError::InvalidInput {
+        reason: e.to_string(),
+    })?;
 
-    for _i in 0..100 {
-        for robot in robots.iter_mut() {
-            robot.position = (robot.position + robot.velocity).rem_euclid(MAP_SIZE);
-        }
+    let mut robots = Robots::new(robots, map_size)?;
+    for _i in 0..params.seconds {
+        robots.step();
     }
 
-    let halves = MAP_SIZE / 2;
+    let halves = map_size / 2;
     let quadrants = [
         (0..halves.x, 0..halves.y),
-        ((halves.x + 1)..MAP_SIZE.x, 0..halves.y),
-        (0..halves.x, (halves.y + 1)..MAP_SIZE.y),
-        ((halves.x + 1)..MAP_SIZE.x, (halves.y + 1)..MAP_SIZE.y),
+        ((halves.x + 1)..map_size.x, 0..halves.y),
+        (0..halves.x, (halves.y + 1)..map_size.y),
+        ((halves.x + 1)..map_size.x, (halves.y + 1)..map_size.y),
     ];
 
     let result: usize = quadrants
         .iter()
         .map(|(xs, ys)| {
             robots
+                .robots()
                 .iter()
                 .filter(|Robot { position, .. }| {
                     xs.contains(&position.x) && ys.contains(&position.y)
@@ -46,47 +47,6 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
-#[allow(dead_code)]
-fn debug_grid(robots: &[Robot]) {
-    println!("");
-    for y in 0..MAP_SIZE.y {
-        for x in 0..MAP_SIZE.x {
-            match robots
-                .iter()
-                .filter(|Robot { position, .. }| position.x == x && position.y == y)
-                .count()
-            {
-                0 => print!("."),
-                n => print!("{}", n),
-            }
-        }
-        println!("");
-    }
-}
-
-#[derive(Debug)]
-struct Robot {
-    position: IVec2,
-    velocity: IVec2,
-}
-
-fn parse_ivec2(input: &str) -> IResult<&str, IVec2> {
-    let (input, (x, y)) = separated_pair(complete::i32, tag(","), complete::i32)(input)?;
-    Ok((input, IVec2::new(x, y)))
-}
-
-fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
-    separated_list1(
-        line_ending,
-        separated_pair(
-            preceded(tag("p="), parse_ivec2),
-            space1,
-            preceded(tag("v="), parse_ivec2),
-        )
-        .map(|(position, velocity)| Robot { position, velocity }),
-    )(input)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,7 +65,7 @@ p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
-        assert_eq!("12", process(input)?);
+        assert_eq!("12", process_with(input, Params::EXAMPLE)?);
         Ok(())
     }
 }
@@ -1,119 +1,242 @@
-use std::collections::HashSet;
-
-use glam::IVec2;
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult, Parser,
-};
-
-const MAP_SIZE: IVec2 = if cfg!(test) {
-    IVec2::new(11, 7)
-} else {
-    IVec2::new(101, 103)
-};
+use crate::{robots::Robots, Params, PuzzleError};
+
+pub use crate::robots::parse;
+
+/// How far the spread of the robots along an axis has to
+/// drop below its average over every phase, in standard
+/// deviations, to count as a cluster. Random noise over a
+/// hundred phases stays within about three.
+const MIN_SCORE: f64 = 4.0;
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let input = input.trim();
-    let (_, mut robots) = parse(input).map_err(|e| miette::miette!("parse failed {}", e))?;
+    process_with(input, Params::default())
+}
 
-    let mut i = 0;
-    let last_step = loop {
-        for robot in robots.iter_mut() {
-            robot.position = (robot.position + robot.velocity).rem_euclid(MAP_SIZE);
+pub fn process_with(
+    input: &str,
+    params: Params,
+) -> miette::Result<String> {
+    let Params { map_size, .. } = params;
+    let input = input.trim();
+    let (_, robots) = parse(input).map_err(|e| {
+        PuzzleError::InvalidInput {
+            reason: e.to_string(),
         }
-        i += 1;
+    })?;
 
-        if tree_test(&robots) {
-            break i;
-        }
-    };
+    let robots = Robots::new(robots, map_size)?;
+    Ok(find_tree(&robots)?.to_string())
+}
+
+/// Seconds until the robots first draw a picture, taken as
+/// the time they cluster along both axes.
+///
+/// A robot's column repeats every `width` seconds and its
+/// row every `height` seconds, independently. So the columns
+/// are checked for their tightest phase within `width`
+/// seconds, the rows within `height` seconds, and the two
+/// phases combined with the Chinese Remainder Theorem
+/// instead of stepping through every second until the
+/// picture shows up.
+pub fn find_tree(
+    robots: &Robots,
+) -> Result<i32, PuzzleError> {
+    let map_size = robots.map_size();
+    let x = clustered_phase('x', map_size.x, |seconds| {
+        robots
+            .positions_at(seconds)
+            .map(|position| position.x)
+            .collect()
+    })?;
+    let y = clustered_phase('y', map_size.y, |seconds| {
+        robots
+            .positions_at(seconds)
+            .map(|position| position.y)
+            .collect()
+    })?;
 
-    Ok(last_step.to_string())
+    crt(x, map_size.x, y, map_size.y)
+        .ok_or(PuzzleError::NoCommonTime { x, y })
 }
 
-fn tree_test(robots: &[Robot]) -> bool {
-    robots
+/// The phase in `0..period` at which the coordinates along
+/// one axis have the smallest variance, if it stands out
+/// from the others.
+fn clustered_phase(
+    axis: char,
+    period: i32,
+    coordinates: impl Fn(i64) -> Vec<i32>,
+) -> Result<i32, PuzzleError> {
+    let variances = (0..period)
+        .map(|seconds| {
+            variance(&coordinates(seconds.into()))
+        })
+        .collect::<Vec<_>>();
+    let mean = variances.iter().sum::<f64>()
+        / variances.len() as f64;
+    let deviation = (variances
         .iter()
-        .map(|Robot { position, .. }| position)
-        .all_unique()
-    // let mut map: HashSet<&IVec2> = HashSet::default();
-    // for Robot { position, .. } in robots {
-    //     match map.contains(position) {
-    //         true => {
-    //             return false;
-    //         }
-    //         false => {
-    //             map.insert(position);
-    //         }
-    //     }
-    // }
-    // true
-}
+        .map(|variance| (variance - mean).powi(2))
+        .sum::<f64>()
+        / variances.len() as f64)
+        .sqrt();
 
-#[allow(dead_code)]
-fn debug_grid(robots: &[Robot]) {
-    println!("");
-    for y in 0..MAP_SIZE.y {
-        for x in 0..MAP_SIZE.x {
-            match robots
-                .iter()
-                .filter(|Robot { position, .. }| position.x == x && position.y == y)
-                .count()
-            {
-                0 => print!("."),
-                n => print!("{}", n),
-            }
-        }
-        println!("");
+    let (phase, best) = variances
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .ok_or(PuzzleError::NoClustering {
+            axis,
+            score: 0.0,
+        })?;
+    let score = if deviation > 0.0 {
+        (mean - best) / deviation
+    } else {
+        0.0
+    };
+    if score < MIN_SCORE {
+        return Err(PuzzleError::NoClustering {
+            axis,
+            score,
+        });
     }
+    Ok(phase as i32)
 }
 
-#[derive(Debug)]
-struct Robot {
-    position: IVec2,
-    velocity: IVec2,
-}
-
-fn parse_ivec2(input: &str) -> IResult<&str, IVec2> {
-    let (input, (x, y)) = separated_pair(complete::i32, tag(","), complete::i32)(input)?;
-    Ok((input, IVec2::new(x, y)))
+fn variance(values: &[i32]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values
+        .iter()
+        .map(|&value| f64::from(value))
+        .sum::<f64>()
+        / n;
+    values
+        .iter()
+        .map(|&value| (f64::from(value) - mean).powi(2))
+        .sum::<f64>()
+        / n
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
-    separated_list1(
-        line_ending,
-        separated_pair(
-            preceded(tag("p="), parse_ivec2),
-            space1,
-            preceded(tag("v="), parse_ivec2),
-        )
-        .map(|(position, velocity)| Robot { position, velocity }),
-    )(input)
+/// The first time that is `x` modulo `width` and `y` modulo
+/// `height`. Of the times `x + k * width` one in every
+/// `height` has the right remainder when the sizes are
+/// coprime, so `height` candidates are enough; otherwise
+/// there may be none.
+fn crt(
+    x: i32,
+    width: i32,
+    y: i32,
+    height: i32,
+) -> Option<i32> {
+    (0..height)
+        .map(|k| x + k * width)
+        .find(|seconds| seconds.rem_euclid(height) == y)
 }
 
 #[cfg(test)]
 mod tests {
+    use glam::IVec2;
+
     use super::*;
+    use crate::robots::Robot;
+
+    /// Robots spread at random, apart from `clustered` of
+    /// them that meet in a 20 by 20 square after `seconds`.
+    fn robots(
+        count: usize,
+        clustered: usize,
+        seconds: i64,
+    ) -> Robots {
+        let map_size = Params::INPUT.map_size;
+        // A fixed linear congruential generator keeps the
+        // test reproducible without another dependency.
+        let mut state = 0x2545_f491_u64;
+        let mut random = |below: i32| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % below as u64) as i32
+        };
+
+        let robots = (0..count)
+            .map(|i| {
+                let velocity = IVec2::new(
+                    random(201) - 100,
+                    random(201) - 100,
+                );
+                let position = if i < clustered {
+                    // Placed in the square, then moved back.
+                    Robot {
+                        position: IVec2::new(
+                            40 + random(20),
+                            60 + random(20),
+                        ),
+                        velocity,
+                    }
+                    .position_at(-seconds, map_size)
+                } else {
+                    IVec2::new(
+                        random(map_size.x),
+                        random(map_size.y),
+                    )
+                };
+                Robot { position, velocity }
+            })
+            .collect();
+        Robots::new(robots, map_size).unwrap()
+    }
 
-    //     #[test]
-    //     fn test_process() -> miette::Result<()> {
-    //         let input = "p=0,4 v=3,-3
-    // p=6,3 v=-1,-3
-    // p=10,3 v=-1,2
-    // p=2,0 v=2,-1
-    // p=0,0 v=1,3
-    // p=3,0 v=-2,-2
-    // p=7,6 v=-1,-3
-    // p=3,0 v=-1,-2
-    // p=9,3 v=2,3
-    // p=7,3 v=-1,2
-    // p=2,4 v=2,-3
-    // p=9,5 v=-3,-3";
-    //         assert_eq!("12", process(input)?);
-    //         Ok(())
-    //     }
+    #[test]
+    fn test_find_tree() {
+        assert_eq!(
+            Ok(6_789),
+            find_tree(&robots(500, 150, 6_789))
+        );
+    }
+
+    #[test]
+    fn test_no_tree() {
+        assert!(matches!(
+            find_tree(&robots(500, 0, 0)),
+            Err(PuzzleError::NoClustering {
+                axis: 'x',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(
+            Some(6_789),
+            crt(6_789 % 101, 101, 6_789 % 103, 103)
+        );
+        assert_eq!(Some(0), crt(0, 101, 0, 103));
+        // Even and odd at once.
+        assert_eq!(None, crt(1, 4, 2, 6));
+        assert_eq!(Some(10), crt(2, 4, 4, 6));
+    }
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = robots(500, 150, 6_789)
+            .robots()
+            .iter()
+            .map(|Robot { position, velocity }| {
+                format!(
+                    "p={},{} v={},{}",
+                    position.x,
+                    position.y,
+                    velocity.x,
+                    velocity.y
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            "6789",
+            process_with(&input, Params::INPUT)?
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,286 @@
+//! The robots moving through the bathroom, one second at a
+//! time.
+
+use glam::IVec2;
+use grid::Grid;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    IResult, Parser,
+};
+use simulation::Simulation;
+
+use crate::PuzzleError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robot {
+    pub position: IVec2,
+    pub velocity: IVec2,
+}
+
+impl Robot {
+    /// Where the robot is `seconds` from now, which can be
+    /// any number of seconds, even negative, without
+    /// stepping through them. Both sides of `map_size` have
+    /// to be positive.
+    pub fn position_at(
+        &self,
+        seconds: i64,
+        map_size: IVec2,
+    ) -> IVec2 {
+        // Each axis repeats after its own size, so the
+        // position, velocity and `seconds` are reduced by
+        // it first, leaving a product below the size
+        // squared, which fits in an `i64`.
+        let axis =
+            |position: i32, velocity: i32, size: i32| {
+                let size = i64::from(size);
+                let position =
+                    i64::from(position).rem_euclid(size);
+                let velocity =
+                    i64::from(velocity).rem_euclid(size);
+                let t = seconds.rem_euclid(size);
+                ((position + velocity * t) % size) as i32
+            };
+        IVec2::new(
+            axis(
+                self.position.x,
+                self.velocity.x,
+                map_size.x,
+            ),
+            axis(
+                self.position.y,
+                self.velocity.y,
+                map_size.y,
+            ),
+        )
+    }
+}
+
+/// Every robot in a space that wraps around at its edges.
+#[derive(Debug, Clone)]
+pub struct Robots {
+    robots: Vec<Robot>,
+    map_size: IVec2,
+    seconds: i32,
+}
+
+impl Robots {
+    /// Wraps robots that start outside the space back into
+    /// it, as they would be after moving there. The space
+    /// needs at least one cell along each axis.
+    pub fn new(
+        mut robots: Vec<Robot>,
+        map_size: IVec2,
+    ) -> Result<Self, PuzzleError> {
+        if map_size.cmple(IVec2::ZERO).any() {
+            return Err(PuzzleError::MapSize { map_size });
+        }
+        for robot in &mut robots {
+            robot.position =
+                robot.position.rem_euclid(map_size);
+        }
+        Ok(Self {
+            robots,
+            map_size,
+            seconds: 0,
+        })
+    }
+
+    pub fn robots(&self) -> &[Robot] {
+        &self.robots
+    }
+
+    pub fn map_size(&self) -> IVec2 {
+        self.map_size
+    }
+
+    /// Every robot's position `seconds` from now, see
+    /// [`Robot::position_at`].
+    pub fn positions_at(
+        &self,
+        seconds: i64,
+    ) -> impl Iterator<Item = IVec2> + '_ {
+        self.robots.iter().map(move |robot| {
+            robot.position_at(seconds, self.map_size)
+        })
+    }
+
+    /// Seconds the robots have moved for.
+    pub fn seconds(&self) -> i32 {
+        self.seconds
+    }
+
+    /// Seconds after which every robot is back where it
+    /// started: one per cell of the space, as each robot
+    /// returns to its column every width and to its row
+    /// every height seconds.
+    pub fn period(&self) -> i32 {
+        self.map_size.x * self.map_size.y
+    }
+}
+
+impl Simulation for Robots {
+    /// Moves every robot by its velocity. Robots never
+    /// stop, so [`Simulation::run`] would not return.
+    fn step(&mut self) -> bool {
+        for robot in self.robots.iter_mut() {
+            robot.position =
+                robot.position_at(1, self.map_size);
+        }
+        self.seconds += 1;
+        true
+    }
+
+    /// How many robots are on each cell, `.` for none and
+    /// `+` for more than nine.
+    fn render(&self) -> Grid<char> {
+        let mut counts = Grid::new(
+            self.map_size.x as usize,
+            self.map_size.y as usize,
+            0,
+        );
+        for Robot { position, .. } in &self.robots {
+            counts[*position] += 1;
+        }
+        counts.map(|&count| match count {
+            0 => '.',
+            n => char::from_digit(n, 10).unwrap_or('+'),
+        })
+    }
+
+    fn status(&self) -> String {
+        format!("{} seconds", self.seconds)
+    }
+}
+
+fn parse_ivec2(input: &str) -> IResult<&str, IVec2> {
+    let (input, (x, y)) = separated_pair(
+        complete::i32,
+        tag(","),
+        complete::i32,
+    )(input)?;
+    Ok((input, IVec2::new(x, y)))
+}
+
+pub fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
+    separated_list1(
+        line_ending,
+        separated_pair(
+            preceded(tag("p="), parse_ivec2),
+            space1,
+            preceded(tag("v="), parse_ivec2),
+        )
+        .map(|(position, velocity)| Robot {
+            position,
+            velocity,
+        }),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let (_, robots) =
+            parse("p=2,4 v=2,-3\np=0,0 v=1,1\np=1,1 v=0,0")
+                .unwrap();
+        let mut robots =
+            Robots::new(robots, IVec2::new(11, 7)).unwrap();
+        robots.step();
+        assert_eq!(
+            "...........\n.2..1......\n...........\n...........\n...........\n...........\n...........",
+            robots.render().to_string()
+        );
+        for _ in 1..robots.period() {
+            robots.step();
+        }
+        assert_eq!(
+            IVec2::new(2, 4),
+            robots.robots()[0].position
+        );
+    }
+
+    #[test]
+    fn test_start_outside() {
+        let (_, robots) =
+            parse("p=200,5 v=1,1\np=-1,-8 v=0,0").unwrap();
+        let robots =
+            Robots::new(robots, IVec2::new(11, 7)).unwrap();
+        assert_eq!(
+            "...........\n...........\n...........\n...........\n...........\n..1........\n..........1",
+            robots.render().to_string()
+        );
+    }
+
+    #[test]
+    fn test_position_at() {
+        let robot = Robot {
+            position: IVec2::new(2, 4),
+            velocity: IVec2::new(2, -3),
+        };
+        let map_size = IVec2::new(11, 7);
+
+        // The example's robot, one to five seconds later.
+        for (seconds, position) in [
+            (1, (4, 1)),
+            (2, (6, 5)),
+            (3, (8, 2)),
+            (4, (10, 6)),
+            (5, (1, 3)),
+        ] {
+            assert_eq!(
+                IVec2::from(position),
+                robot.position_at(seconds, map_size)
+            );
+        }
+        assert_eq!(
+            IVec2::new(2, 4),
+            robot.position_at(77_000_000_000, map_size)
+        );
+        assert_eq!(
+            robot.position_at(-1, map_size),
+            robot.position_at(76, map_size)
+        );
+    }
+
+    #[test]
+    fn test_large_velocity() {
+        let robot = Robot {
+            position: IVec2::new(i32::MAX, 4),
+            velocity: IVec2::new(i32::MAX, i32::MIN),
+        };
+        let map_size = IVec2::new(101, 103);
+        let position = robot.position_at(1_000, map_size);
+        let expected = |p: i64, v: i64, size: i64| {
+            (p + v * 1_000).rem_euclid(size) as i32
+        };
+        assert_eq!(
+            IVec2::new(
+                expected(
+                    i32::MAX.into(),
+                    i32::MAX.into(),
+                    101
+                ),
+                expected(4, i32::MIN.into(), 103),
+            ),
+            position
+        );
+    }
+
+    #[test]
+    fn test_empty_space() {
+        for map_size in
+            [IVec2::new(0, 7), IVec2::new(11, -1)]
+        {
+            assert_eq!(
+                Some(PuzzleError::MapSize { map_size }),
+                Robots::new(vec![], map_size).err()
+            );
+        }
+    }
+}
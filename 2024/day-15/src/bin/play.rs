@@ -0,0 +1,10 @@
+use day_15::{part2::parse, warehouse::Warehouse};
+
+/// Shows the robot working through the widened warehouse of
+/// part 2 move by move.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let (map, moves) = parse(file.trim())?;
+    simulation::play(Warehouse::new(map, moves)?)?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::io::{self, BufRead, Write};
+
+use day_15::warehouse::Warehouse;
+use miette::{miette, IntoDiagnostic};
+use simulation::Simulation;
+
+const HELP: &str =
+    "^v<>     move the robot, any number of moves at once
+step [n] make the next n moves of the move list, default 1
+run      make every remaining move of the move list
+undo [n] take back the last n moves, default 1
+widen [n]      start over with the map n times as wide,
+               default 2 as in part 2
+save <file>    write the map and remaining moves
+log <file>     write the moves made so far
+replay <file>  make every move of a log
+quit";
+
+/// Drives the robot through a warehouse from commands on
+/// stdin, for building and debugging layouts. The input can
+/// be a puzzle input or a map without moves.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let mut warehouse: Warehouse = file.parse()?;
+    println!(
+        "{HELP}\n\n{}\n{}",
+        warehouse.render(),
+        warehouse.status()
+    );
+
+    let mut stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let line = line.into_diagnostic()?;
+        match command(&mut warehouse, line.trim()) {
+            Ok(true) => break,
+            Ok(false) => println!(
+                "{}\n{}",
+                warehouse.render(),
+                warehouse.status()
+            ),
+            Err(error) => println!("{error:?}"),
+        }
+        stdout.flush().into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Runs one command, `true` when it asks to quit.
+fn command(
+    warehouse: &mut Warehouse,
+    line: &str,
+) -> miette::Result<bool> {
+    let (name, argument) =
+        line.split_once(' ').unwrap_or((line, ""));
+    let count = || -> miette::Result<usize> {
+        match argument {
+            "" => Ok(1),
+            n => n.parse().into_diagnostic(),
+        }
+    };
+    let path = || match argument {
+        "" => Err(miette!("`{name}` needs a file")),
+        path => Ok(path),
+    };
+
+    match name {
+        "" => {}
+        "quit" | "q" => return Ok(true),
+        "help" | "?" => println!("{HELP}"),
+        "step" => {
+            for _ in 0..count()? {
+                if !warehouse.step() {
+                    break;
+                }
+                println!(
+                    "{}",
+                    warehouse
+                        .history()
+                        .last()
+                        .expect("a move was made")
+                );
+            }
+        }
+        "run" => {
+            let moves = warehouse.run();
+            println!("made {moves} moves");
+        }
+        "undo" => {
+            for _ in 0..count()? {
+                match warehouse.undo() {
+                    Some(step) => println!("undid {step}"),
+                    None => break,
+                }
+            }
+        }
+        "widen" => {
+            let factor = match argument {
+                "" => 2,
+                n => n.parse().into_diagnostic()?,
+            };
+            *warehouse = warehouse.widen(factor)?;
+        }
+        "save" => std::fs::write(
+            path()?,
+            format!("{warehouse}\n"),
+        )
+        .into_diagnostic()?,
+        "log" => {
+            std::fs::write(path()?, warehouse.log() + "\n")
+                .into_diagnostic()?
+        }
+        "replay" => {
+            let log = std::fs::read_to_string(path()?)
+                .into_diagnostic()?;
+            for step in warehouse.replay(&log)? {
+                println!("{step}");
+            }
+        }
+        moves => {
+            for step in warehouse.replay(moves)? {
+                println!("{step}");
+            }
+        }
+    }
+    Ok(false)
+}
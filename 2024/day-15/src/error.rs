@@ -0,0 +1,41 @@
+use glam::IVec2;
+use grid::GridError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Map(#[from] GridError),
+    #[error("expected a blank line between the map and the moves")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    MissingMoves,
+    #[error(
+        "the map has {count} robots, it needs exactly one"
+    )]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    Robots { count: usize },
+    #[error(
+        "the box half at {position} has no other half"
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("wide boxes are written `[]`")
+    )]
+    BrokenBox { position: IVec2 },
+    #[error("unexpected move `{0}`")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("moves are `^`, `v`, `<` and `>`, whitespace is ignored")
+    )]
+    InvalidMove(char),
+    #[error(
+        "widening {factor} times makes the map too wide"
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("boxes can be at most 65535 cells wide")
+    )]
+    TooWide { factor: u16 },
+}
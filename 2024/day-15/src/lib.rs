@@ -1,2 +1,6 @@
+pub mod error;
 pub mod part1;
 pub mod part2;
+pub mod warehouse;
+
+pub use error::PuzzleError;
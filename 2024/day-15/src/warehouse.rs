@@ -0,0 +1,800 @@
+//! The warehouse robot pushing boxes around, one move at a
+//! time. Both halves of the puzzle share it: part 2 only
+//! widens the map first.
+//!
+//! Boxes can be any number of cells wide. A box one cell
+//! wide is written `O` as in part 1, wider ones as `[]`,
+//! `[=]`, `[==]` and so on, and pushing any cell of a box
+//! pushes all of it.
+//!
+//! Besides running the puzzle's move list, a [`Warehouse`]
+//! takes moves one by one, remembers what each of them
+//! pushed so that it can be undone, and reads and writes
+//! its state in the puzzle's own text format, which makes
+//! it usable for trying out hand-made layouts.
+
+use std::{collections::HashSet, fmt, str::FromStr};
+
+use glam::IVec2;
+use grid::Grid;
+use simulation::Simulation;
+
+use crate::PuzzleError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Object {
+    Empty,
+    Wall,
+    /// One cell of a box `width` cells wide, `offset` cells
+    /// right of its left edge.
+    Box {
+        offset: u16,
+        width: u16,
+    },
+    Robot,
+}
+
+impl Object {
+    fn to_char(self) -> char {
+        match self {
+            Object::Empty => '.',
+            Object::Wall => '#',
+            Object::Box { width: 1, .. } => 'O',
+            Object::Box { offset: 0, .. } => '[',
+            Object::Box { offset, width }
+                if offset + 1 == width =>
+            {
+                ']'
+            }
+            Object::Box { .. } => '=',
+            Object::Robot => '@',
+        }
+    }
+
+    /// Whether this is the left edge of a box, where its
+    /// GPS coordinate is measured.
+    fn is_box_edge(&self) -> bool {
+        matches!(self, Object::Box { offset: 0, .. })
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// The four ways the robot can move, one cell at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn to_ivec2(self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::NEG_Y,
+            Direction::Down => IVec2::Y,
+            Direction::Left => IVec2::NEG_X,
+            Direction::Right => IVec2::X,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = PuzzleError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(Direction::Up),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            '>' => Ok(Direction::Right),
+            other => Err(PuzzleError::InvalidMove(other)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// What one move of the robot did, enough to take it back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub direction: Direction,
+    /// Everything that moved, at its position before the
+    /// move: the robot first, then the boxes in the order
+    /// they were pushed. Empty when a wall was in the way.
+    moved: Vec<(IVec2, Object)>,
+    /// Whether the move came from the move list, which
+    /// gets it back when the move is undone.
+    queued: bool,
+}
+
+impl Move {
+    pub fn is_blocked(&self) -> bool {
+        self.moved.is_empty()
+    }
+
+    /// Where each box that moved was before the move, by
+    /// its left edge.
+    pub fn boxes(
+        &self,
+    ) -> impl Iterator<Item = IVec2> + '_ {
+        self.moved
+            .iter()
+            .filter(|(_, obj)| obj.is_box_edge())
+            .map(|(pos, _)| *pos)
+    }
+}
+
+/// `> pushed 2 boxes: [3, 4] [5, 4]`, positions before the
+/// move.
+impl fmt::Display for Move {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let direction = self.direction;
+        if self.is_blocked() {
+            return write!(f, "{direction} blocked");
+        }
+        let boxes = self.boxes().collect::<Vec<_>>();
+        match boxes.len() {
+            0 => write!(f, "{direction} moved"),
+            1 => write!(
+                f,
+                "{direction} pushed 1 box: {}",
+                boxes[0]
+            ),
+            n => {
+                write!(f, "{direction} pushed {n} boxes:")?;
+                for pos in boxes {
+                    write!(f, " {pos}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The map with the robot on it, the moves it has yet to
+/// try and the ones it made.
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    map: Grid<Object>,
+    robot: IVec2,
+    moves: Vec<Direction>,
+    next: usize,
+    history: Vec<Move>,
+}
+
+impl Warehouse {
+    /// Checks that the map has one robot and only whole
+    /// boxes.
+    pub fn new(
+        map: Grid<Object>,
+        moves: Vec<Direction>,
+    ) -> Result<Self, PuzzleError> {
+        let robots = map
+            .iter()
+            .filter(|(_, obj)| obj == &&Object::Robot)
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        let [robot] = robots[..] else {
+            return Err(PuzzleError::Robots {
+                count: robots.len(),
+            });
+        };
+        for (position, obj) in map.iter() {
+            let Object::Box { offset, width } = *obj else {
+                continue;
+            };
+            let cell = |dx: i32, offset: u16| {
+                map.get(position + IVec2::X * dx)
+                    == Some(&Object::Box { offset, width })
+            };
+            let whole = offset < width
+                && (offset == 0 || cell(-1, offset - 1))
+                && (offset + 1 == width
+                    || cell(1, offset + 1));
+            if !whole {
+                return Err(PuzzleError::BrokenBox {
+                    position,
+                });
+            }
+        }
+
+        Ok(Self {
+            map,
+            robot,
+            moves,
+            next: 0,
+            history: vec![],
+        })
+    }
+
+    pub fn map(&self) -> &Grid<Object> {
+        &self.map
+    }
+
+    pub fn robot(&self) -> IVec2 {
+        self.robot
+    }
+
+    /// Moves of the move list that have not been made yet.
+    pub fn remaining(&self) -> &[Direction] {
+        &self.moves[self.next..]
+    }
+
+    /// Every move made so far, oldest first.
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// Sum of the GPS coordinates of every box, measured
+    /// from its left edge.
+    pub fn gps(&self) -> i64 {
+        self.map
+            .iter()
+            .filter(|(_, obj)| obj.is_box_edge())
+            .map(|(pos, _)| {
+                100 * i64::from(pos.y) + i64::from(pos.x)
+            })
+            .sum()
+    }
+
+    /// Starts over on the map `factor` times as wide, see
+    /// [`widen`], with the moves not made yet.
+    pub fn widen(
+        &self,
+        factor: u16,
+    ) -> Result<Warehouse, PuzzleError> {
+        Ok(Warehouse {
+            map: widen(&self.map, factor)?,
+            robot: self.robot
+                * IVec2::new(factor.max(1).into(), 1),
+            moves: self.remaining().to_vec(),
+            next: 0,
+            history: vec![],
+        })
+    }
+
+    /// Moves the robot one cell in `direction`, whether or
+    /// not that is next in the move list.
+    pub fn apply(&mut self, direction: Direction) -> &Move {
+        self.push(direction, false)
+    }
+
+    /// Takes back the last move, `None` when there is none.
+    pub fn undo(&mut self) -> Option<Move> {
+        let last = self.history.pop()?;
+        // The robot moves back first, making room for the
+        // boxes behind it.
+        let step = last.direction.to_ivec2();
+        for &(pos, _) in &last.moved {
+            self.map.swap(pos + step, pos);
+        }
+        if !last.is_blocked() {
+            self.robot -= step;
+        }
+        if last.queued {
+            self.next -= 1;
+        }
+        Some(last)
+    }
+
+    /// The moves made so far in the puzzle's notation, to
+    /// be [replayed](Warehouse::replay) later.
+    pub fn log(&self) -> String {
+        self.history
+            .iter()
+            .map(|step| step.direction.to_char())
+            .collect()
+    }
+
+    /// Makes every move of `log`, returning what they did.
+    /// Nothing moves when the log does not parse.
+    pub fn replay(
+        &mut self,
+        log: &str,
+    ) -> Result<&[Move], PuzzleError> {
+        let directions = parse_moves(log)?;
+        let start = self.history.len();
+        for direction in directions {
+            self.apply(direction);
+        }
+        Ok(&self.history[start..])
+    }
+
+    /// Moves the robot and every box in its way, unless
+    /// one of them is blocked by a wall.
+    fn push(
+        &mut self,
+        direction: Direction,
+        queued: bool,
+    ) -> &Move {
+        let step = direction.to_ivec2();
+        // Everything that has to move, found in the order
+        // the robot reaches it, so the reverse order moves
+        // each one into a cell that is already empty.
+        let mut moving = vec![(self.robot, Object::Robot)];
+        // Box cells already in `moving`, reached again
+        // through the other cells of their box.
+        let mut seen = HashSet::new();
+        let mut i = 0;
+        let blocked = loop {
+            let Some(&(pos, _)) = moving.get(i) else {
+                break false;
+            };
+            i += 1;
+            let next_pos = pos + step;
+            // Hand-made layouts need no wall around them,
+            // and leaving the map is as blocked as a wall.
+            match self.map.get(next_pos) {
+                None | Some(Object::Wall) => break true,
+                Some(Object::Empty) => {}
+                Some(&Object::Box { offset, width }) => {
+                    // Cells further along `direction`
+                    // come later, to be moved first.
+                    let left =
+                        next_pos.x - i32::from(offset);
+                    let cells =
+                        left..left + i32::from(width);
+                    let cells: Vec<_> =
+                        if direction == Direction::Left {
+                            cells.rev().collect()
+                        } else {
+                            cells.collect()
+                        };
+                    for x in cells {
+                        let pos = IVec2::new(x, next_pos.y);
+                        if seen.insert(pos) {
+                            moving
+                                .push((pos, self.map[pos]));
+                        }
+                    }
+                }
+                Some(Object::Robot) => {
+                    unreachable!(
+                        "should never see a second robot"
+                    );
+                }
+            }
+        };
+
+        if blocked {
+            moving.clear();
+        } else {
+            for &(pos, _) in moving.iter().rev() {
+                self.map.swap(pos, pos + step);
+            }
+            self.robot += step;
+        }
+        self.history.push(Move {
+            direction,
+            moved: moving,
+            queued,
+        });
+        self.history.last().expect("a move was just made")
+    }
+}
+
+impl Simulation for Warehouse {
+    fn step(&mut self) -> bool {
+        let Some(&direction) = self.moves.get(self.next)
+        else {
+            return false;
+        };
+        self.next += 1;
+        self.push(direction, true);
+        true
+    }
+
+    fn render(&self) -> Grid<char> {
+        self.map.map(|obj| obj.to_char())
+    }
+
+    fn status(&self) -> String {
+        let next = self
+            .remaining()
+            .first()
+            .map(|direction| format!(" next {direction}"))
+            .unwrap_or_default();
+        let last = self
+            .history
+            .last()
+            .map(|step| format!(" | {step}"))
+            .unwrap_or_default();
+        format!(
+            "move {}/{}{next} | gps {}{last}",
+            self.next,
+            self.moves.len(),
+            self.gps()
+        )
+    }
+}
+
+/// The map, then the moves not made yet after a blank line
+/// in lines of 70 like the puzzle's, which reads back with
+/// [`FromStr`].
+impl fmt::Display for Warehouse {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.map)?;
+        let remaining = self.remaining();
+        if !remaining.is_empty() {
+            writeln!(f)?;
+            for line in remaining.chunks(70) {
+                writeln!(f)?;
+                for direction in line {
+                    write!(f, "{direction}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A map in the puzzle's format, with or without moves
+/// after it.
+impl FromStr for Warehouse {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (map, moves) =
+            s.split_once("\n\n").unwrap_or((s, ""));
+        Warehouse::new(parse_map(map)?, parse_moves(moves)?)
+    }
+}
+
+/// The map with every tile `factor` times as wide, a box
+/// of width 1 turning into one of width `factor`. The robot
+/// stays one cell wide, on the left of its widened tile.
+/// Fails when a box or the map would get wider than their
+/// widths can hold.
+pub fn widen(
+    map: &Grid<Object>,
+    factor: u16,
+) -> Result<Grid<Object>, PuzzleError> {
+    let factor = factor.max(1);
+    let too_wide = || PuzzleError::TooWide { factor };
+    let widest_box = map
+        .cells()
+        .iter()
+        .filter_map(|obj| match obj {
+            Object::Box { width, .. } => Some(*width),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(1);
+    widest_box.checked_mul(factor).ok_or_else(too_wide)?;
+    let width = map
+        .width()
+        .checked_mul(usize::from(factor))
+        .filter(|&width| i32::try_from(width).is_ok())
+        .ok_or_else(too_wide)?;
+    // Every box fits, so neither its width nor any of its
+    // offsets overflow.
+    let cells = map
+        .cells()
+        .iter()
+        .flat_map(|&obj| {
+            (0..factor).map(move |i| match obj {
+                Object::Robot if i > 0 => Object::Empty,
+                Object::Box { offset, width } => {
+                    Object::Box {
+                        offset: offset * factor + i,
+                        width: width * factor,
+                    }
+                }
+                other => other,
+            })
+        })
+        .collect();
+    Ok(
+        Grid::from_vec(width, map.height(), cells)
+            .expect("every row to grow by the same factor"),
+    )
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<(Grid<Object>, Vec<Direction>), PuzzleError> {
+    let (map, moves) = input
+        .split_once("\n\n")
+        .ok_or(PuzzleError::MissingMoves)?;
+
+    Ok((parse_map(map)?, parse_moves(moves)?))
+}
+
+/// A map in the puzzle's format, with boxes of any width.
+pub fn parse_map(
+    input: &str,
+) -> Result<Grid<Object>, PuzzleError> {
+    let chars = Grid::try_parse(input, |c| {
+        "#.@O[=]".contains(c).then_some(c)
+    })?;
+
+    let mut cells = Vec::with_capacity(chars.cells().len());
+    for (y, row) in chars.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let position = IVec2::new(x as i32, y as i32);
+            match row[x] {
+                '#' => cells.push(Object::Wall),
+                '.' => cells.push(Object::Empty),
+                '@' => cells.push(Object::Robot),
+                'O' => cells.push(Object::Box {
+                    offset: 0,
+                    width: 1,
+                }),
+                '[' => {
+                    let inside = row[x + 1..]
+                        .iter()
+                        .take_while(|&&c| c == '=')
+                        .count();
+                    let end = x + 1 + inside;
+                    if row.get(end) != Some(&']') {
+                        return Err(
+                            PuzzleError::BrokenBox {
+                                position,
+                            },
+                        );
+                    }
+                    let width = (end - x + 1) as u16;
+                    cells.extend((0..width).map(
+                        |offset| Object::Box {
+                            offset,
+                            width,
+                        },
+                    ));
+                    x = end;
+                }
+                _ => {
+                    return Err(PuzzleError::BrokenBox {
+                        position,
+                    })
+                }
+            }
+            x += 1;
+        }
+    }
+
+    Ok(Grid::from_vec(
+        chars.width(),
+        chars.height(),
+        cells,
+    )?)
+}
+
+/// Moves written as `^v<>`, on any number of lines.
+pub fn parse_moves(
+    input: &str,
+) -> Result<Vec<Direction>, PuzzleError> {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(Direction::try_from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: &str = "#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^";
+
+    #[test]
+    fn test_wide_push() -> miette::Result<()> {
+        let mut warehouse =
+            LAYOUT.parse::<Warehouse>()?.widen(2)?;
+
+        warehouse.step();
+        assert_eq!(
+            "move 1/11 next v | gps 1018 | < pushed 2 boxes: [8, 3] [6, 3]",
+            warehouse.status()
+        );
+        warehouse.run();
+        assert_eq!(
+            "##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############",
+            warehouse.to_string()
+        );
+        assert_eq!(105 + 207 + 306, warehouse.gps());
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo() -> miette::Result<()> {
+        let mut warehouse =
+            LAYOUT.parse::<Warehouse>()?.widen(2)?;
+        let start = warehouse.to_string();
+
+        // The third box is pushed up by both boxes above,
+        // then all three are blocked by the wall.
+        warehouse.replay("<vv<<^")?;
+        let pushed = warehouse.history().last().unwrap();
+        assert_eq!(
+            vec![
+                IVec2::new(6, 4),
+                IVec2::new(5, 3),
+                IVec2::new(7, 3)
+            ],
+            pushed.boxes().collect::<Vec<_>>()
+        );
+        assert!(warehouse
+            .apply(Direction::Up)
+            .is_blocked());
+        let pushed = warehouse.to_string();
+
+        // Manual moves leave the move list alone.
+        assert_eq!("<vv<<^^", warehouse.log());
+        assert_eq!(11, warehouse.remaining().len());
+
+        assert!(warehouse.undo().unwrap().is_blocked());
+        assert_eq!(pushed, warehouse.to_string());
+        while warehouse.undo().is_some() {}
+        assert_eq!(start, warehouse.to_string());
+        assert_eq!(IVec2::new(10, 3), warehouse.robot());
+
+        warehouse.step();
+        warehouse.undo();
+        assert_eq!(start, warehouse.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let mut warehouse: Warehouse = LAYOUT.parse()?;
+        warehouse.step();
+        warehouse.step();
+
+        let saved = warehouse.to_string();
+        assert_eq!(
+            "#######
+#...#.#
+#.....#
+#.OO..#
+#..O@.#
+#.....#
+#######
+
+v<<^^<<^^",
+            saved
+        );
+        let loaded: Warehouse = saved.parse()?;
+        assert_eq!(saved, loaded.to_string());
+
+        let map_only: Warehouse =
+            saved.split_once("\n\n").unwrap().0.parse()?;
+        assert!(map_only.remaining().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_n_wide_boxes() -> miette::Result<()> {
+        // A box three wide lifts both boxes above its ends,
+        // which then hit the wall.
+        let mut warehouse: Warehouse = "#######
+#.....#
+#.O.O.#
+#.[=].#
+#..@..#
+#######
+
+^^"
+        .parse()?;
+        warehouse.run();
+        assert_eq!(
+            "#######
+#.O.O.#
+#.[=].#
+#..@..#
+#.....#
+#######",
+            warehouse.to_string()
+        );
+        assert_eq!(102 + 104 + 202, warehouse.gps());
+        assert!(warehouse.history()[1].is_blocked());
+
+        let mut wide =
+            "#.O@#\n\n<<".parse::<Warehouse>()?.widen(3)?;
+        assert_eq!(
+            "###...[=]@..###\n\n<<",
+            wide.to_string()
+        );
+        wide.run();
+        assert_eq!("###.[=]@....###", wide.to_string());
+        assert_eq!(4, wide.gps());
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_wide() -> miette::Result<()> {
+        let warehouse =
+            "#.O@#".parse::<Warehouse>()?.widen(300)?;
+        assert_eq!(
+            Some(PuzzleError::TooWide { factor: 300 }),
+            warehouse.widen(300).err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_border() -> miette::Result<()> {
+        let mut warehouse: Warehouse =
+            "#@.\n\n>>".parse()?;
+        warehouse.run();
+        assert_eq!("#.@", warehouse.to_string());
+        assert!(warehouse.history()[1].is_blocked());
+
+        let mut warehouse: Warehouse =
+            "@O.\n\n>>^<".parse()?;
+        warehouse.run();
+        assert_eq!("@.O", warehouse.to_string());
+        assert_eq!(2, warehouse.gps());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_layouts() {
+        assert_eq!(
+            Err(PuzzleError::Robots { count: 0 }),
+            "#..#".parse::<Warehouse>().map(|_| ())
+        );
+        assert_eq!(
+            Err(PuzzleError::BrokenBox {
+                position: IVec2::new(2, 0)
+            }),
+            "#@[.]#".parse::<Warehouse>().map(|_| ())
+        );
+        assert_eq!(
+            Err(PuzzleError::BrokenBox {
+                position: IVec2::new(3, 0)
+            }),
+            "#@.=]#".parse::<Warehouse>().map(|_| ())
+        );
+        assert_eq!(
+            Err(PuzzleError::InvalidMove('x')),
+            "#@.#\n\n<>x".parse::<Warehouse>().map(|_| ())
+        );
+    }
+}
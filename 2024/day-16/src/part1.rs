@@ -1,100 +1,65 @@
-use std::collections::HashSet;
-
 use glam::IVec2;
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::{all_consuming, opt},
-    multi::{many1, separated_list1},
-    IResult,
+use grid::{
+    path::{GridGraph, Heading},
+    Grid,
 };
-use nom_locate::LocatedSpan;
-use pathfinding::prelude::*;
+
+use crate::PuzzleError;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, Map { start, end, walls }) =
-        all_consuming(parse)(Span::new(input)).map_err(
-            |e| miette::miette!("failed parsing {}", e),
-        )?;
-
-    let result = dijkstra(
-        &(start, IVec2::X),
-        |(position, direction)| {
-            let next_pos = position + direction;
-            if walls.contains(&next_pos) {
-                vec![
-                    ((*position, direction.perp()), 1000),
-                    ((*position, -direction.perp()), 1000),
-                ]
-            } else {
-                vec![
-                    ((next_pos, *direction), 1),
-                    ((*position, direction.perp()), 1000),
-                    ((*position, -direction.perp()), 1000),
-                ]
-            }
-        },
-        |&(pos, _)| pos == end,
-    )
-    .expect("a valid path result");
-
-    Ok(result.1.to_string())
-}
+    let map = parse(input)?;
 
-pub type Span<'a> = LocatedSpan<&'a str>;
+    let (_path, cost) = map
+        .graph()
+        .shortest_path(
+            Heading::new(map.start, IVec2::X),
+            |state| state.position == map.end,
+        )
+        .ok_or(PuzzleError::NoPath)?;
 
-fn token(input: Span) -> IResult<Span, (IVec2, char)> {
-    let y = input.location_line();
-    let x = input.get_column();
-    let (input, token) = one_of(".#SE")(input)?;
-
-    Ok((
-        input,
-        (
-            IVec2::new(x as i32 - 1, y as i32 - 1),
-            token,
-        ),
-    ))
+    Ok(cost.to_string())
 }
 
 pub struct Map {
     pub start: IVec2,
     pub end: IVec2,
-    pub walls: HashSet<IVec2>,
+    pub walls: Grid<bool>,
 }
-pub fn parse(input: Span) -> IResult<Span, Map> {
-    let (input, items) =
-        separated_list1(line_ending, many1(token))(input)?;
-    let (input, _) = opt(line_ending)(input)?;
 
-    let (starting_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'S')
-        .cloned()
-        .expect("should have a start");
-    let (ending_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'E')
-        .cloned()
-        .expect("should have an end");
-    let walls = items
-        .into_iter()
-        .flatten()
-        .filter_map(|(pos, val)| {
-            (val == '#').then_some(pos)
-        })
-        .collect::<HashSet<IVec2>>();
+impl Map {
+    /// The reindeer moves between open tiles for 1 point and
+    /// turns for 1000.
+    pub fn graph(
+        &self,
+    ) -> GridGraph<
+        '_,
+        bool,
+        Heading,
+        impl Fn(IVec2, &bool) -> bool,
+    > {
+        GridGraph::new(&self.walls, |_, wall| !wall)
+            .turn_cost(1000)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Map, PuzzleError> {
+    let grid = Grid::try_parse(input, |c| {
+        ".#SE".contains(c).then_some(c)
+    })?;
+
+    let start = grid
+        .find(|c| c == &'S')
+        .ok_or(PuzzleError::MissingTile { tile: 'S' })?;
+    let end = grid
+        .find(|c| c == &'E')
+        .ok_or(PuzzleError::MissingTile { tile: 'E' })?;
 
-    Ok((
-        input,
-        Map {
-            start: starting_position,
-            end: ending_position,
-            walls,
-        },
-    ))
+    Ok(Map {
+        start,
+        end,
+        walls: grid.map(|c| c == &'#'),
+    })
 }
 
 #[cfg(test)]
@@ -1,107 +1,29 @@
 use std::collections::HashSet;
 
 use glam::IVec2;
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::{all_consuming, opt},
-    multi::{many1, separated_list1},
-    IResult,
-};
-use nom_locate::LocatedSpan;
-use pathfinding::prelude::*;
+use grid::path::Heading;
+
+use crate::{part1::parse, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, Map { start, end, walls }) =
-        all_consuming(parse)(Span::new(input)).map_err(
-            |e| miette::miette!("failed parsing {}", e),
-        )?;
-
-    let (result, _cost) = astar_bag(
-        &(start, IVec2::X),
-        |(position, direction)| {
-            let next_pos = position + direction;
-            if walls.contains(&next_pos) {
-                vec![
-                    ((*position, direction.perp()), 1000),
-                    ((*position, -direction.perp()), 1000),
-                ]
-            } else {
-                vec![
-                    ((next_pos, *direction), 1),
-                    ((*position, direction.perp()), 1000),
-                    ((*position, -direction.perp()), 1000),
-                ]
-            }
-        },
-        |_| 0,
-        |&(pos, _)| pos == end,
-    )
-    .expect("a valid path result");
-
-    let set = result
-        .flat_map(|path| {
-            path.into_iter().map(|(position, _)| position)
-        })
-        .collect::<HashSet<IVec2>>();
-
-    Ok(set.len().to_string())
-}
-
-pub type Span<'a> = LocatedSpan<&'a str>;
+    let map = parse(input)?;
 
-fn token(input: Span) -> IResult<Span, (IVec2, char)> {
-    let y = input.location_line();
-    let x = input.get_column();
-    let (input, token) = one_of(".#SE")(input)?;
+    let (paths, _cost) = map
+        .graph()
+        .all_shortest_paths(
+            Heading::new(map.start, IVec2::X),
+            |state| state.position == map.end,
+        )
+        .ok_or(PuzzleError::NoPath)?;
 
-    Ok((
-        input,
-        (
-            IVec2::new(x as i32 - 1, y as i32 - 1),
-            token,
-        ),
-    ))
-}
-
-pub struct Map {
-    pub start: IVec2,
-    pub end: IVec2,
-    pub walls: HashSet<IVec2>,
-}
-pub fn parse(input: Span) -> IResult<Span, Map> {
-    let (input, items) =
-        separated_list1(line_ending, many1(token))(input)?;
-    let (input, _) = opt(line_ending)(input)?;
-
-    let (starting_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'S')
-        .cloned()
-        .expect("should have a start");
-    let (ending_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'E')
-        .cloned()
-        .expect("should have an end");
-    let walls = items
+    let set = paths
         .into_iter()
         .flatten()
-        .filter_map(|(pos, val)| {
-            (val == '#').then_some(pos)
-        })
+        .map(|state| state.position)
         .collect::<HashSet<IVec2>>();
 
-    Ok((
-        input,
-        Map {
-            start: starting_position,
-            end: ending_position,
-            walls,
-        },
-    ))
+    Ok(set.len().to_string())
 }
 
 #[cfg(test)]
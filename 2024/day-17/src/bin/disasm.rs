@@ -0,0 +1,13 @@
+use day_17::{disasm::disassemble, part1::parse};
+
+/// Prints the input's program as assembly and as
+/// pseudo-code.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let (_registers, program) = parse(&file)?;
+
+    let disassembly = disassemble(&program)?;
+    println!("{}\n", disassembly.listing());
+    print!("{disassembly}");
+    Ok(())
+}
@@ -2,8 +2,8 @@ use day_17::part1_2::process;
 use miette::Context;
 
 fn main() -> miette::Result<()> {
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let result = process(&file).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
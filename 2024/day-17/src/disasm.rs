@@ -0,0 +1,257 @@
+//! Readable listings of a program, for working out what a
+//! new input does before trusting the quine search with it.
+
+use std::fmt;
+
+use crate::{vm::Instruction, PuzzleError};
+
+/// An operand as the instruction reads it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Literal(u8),
+    Register(char),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Operand::Literal(value) => write!(f, "{value}"),
+            Operand::Register(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// One decoded instruction of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line {
+    pub pointer: usize,
+    pub instruction: Instruction,
+    pub operand: Operand,
+}
+
+impl Line {
+    fn decode(
+        pointer: usize,
+        opcode: u8,
+        operand: u8,
+    ) -> Result<Self, PuzzleError> {
+        let instruction = Instruction::try_from(opcode)
+            .map_err(|_| PuzzleError::InvalidOpcode {
+                pointer,
+                opcode: opcode as usize,
+            })?;
+        let operand = match instruction {
+            Instruction::Bxl
+            | Instruction::Jnz
+            | Instruction::Bxc => Operand::Literal(operand),
+            _ => {
+                match operand {
+                    0..=3 => Operand::Literal(operand),
+                    4 => Operand::Register('a'),
+                    5 => Operand::Register('b'),
+                    6 => Operand::Register('c'),
+                    _ => return Err(
+                        PuzzleError::ReservedComboOperand {
+                            pointer,
+                        },
+                    ),
+                }
+            }
+        };
+        Ok(Self {
+            pointer,
+            instruction,
+            operand,
+        })
+    }
+
+    /// The instruction as assembly, `bst A`.
+    pub fn mnemonic(&self) -> String {
+        let name = format!("{:?}", self.instruction)
+            .to_lowercase();
+        match self.operand {
+            Operand::Register(register) => {
+                format!(
+                    "{name} {}",
+                    register.to_ascii_uppercase()
+                )
+            }
+            Operand::Literal(value) => {
+                format!("{name} {value}")
+            }
+        }
+    }
+
+    /// What the instruction does, `b = a % 8`.
+    pub fn pseudo_code(&self) -> String {
+        let operand = self.operand;
+        match self.instruction {
+            Instruction::Adv => {
+                format!("a = a >> {operand}")
+            }
+            Instruction::Bxl => {
+                format!("b = b ^ {operand}")
+            }
+            Instruction::Bst => {
+                format!("b = {operand} % 8")
+            }
+            Instruction::Jnz => {
+                format!("if a != 0 goto {operand}")
+            }
+            Instruction::Bxc => "b = b ^ c".to_string(),
+            Instruction::Out => {
+                format!("out({operand} % 8)")
+            }
+            Instruction::Bdv => {
+                format!("b = a >> {operand}")
+            }
+            Instruction::Cdv => {
+                format!("c = a >> {operand}")
+            }
+        }
+    }
+}
+
+/// A decoded program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disassembly {
+    pub lines: Vec<Line>,
+}
+
+impl Disassembly {
+    /// Whether the program is the usual single loop: its
+    /// only jump is `jnz 0`, as the last instruction.
+    pub fn is_loop(&self) -> bool {
+        let mut jumps = self.lines.iter().filter(|line| {
+            line.instruction == Instruction::Jnz
+        });
+        matches!(
+            (jumps.next(), jumps.next()),
+            (Some(jump), None)
+                if jump.operand == Operand::Literal(0)
+                    && Some(jump) == self.lines.last()
+        )
+    }
+
+    /// Pointer, assembly and pseudo-code of every
+    /// instruction, one per line.
+    pub fn listing(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "{:>3}: {:<8}{}",
+                    line.pointer,
+                    line.mnemonic(),
+                    line.pseudo_code()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Pseudo-code of the whole program, with the jump back to
+/// the start written as a `do`/`while` loop when the
+/// program [`is_loop`](Disassembly::is_loop).
+impl fmt::Display for Disassembly {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if !self.is_loop() {
+            for line in &self.lines {
+                writeln!(
+                    f,
+                    "{}: {}",
+                    line.pointer,
+                    line.pseudo_code()
+                )?;
+            }
+            return Ok(());
+        }
+
+        writeln!(f, "do {{")?;
+        for line in &self.lines[..self.lines.len() - 1] {
+            writeln!(f, "    {}", line.pseudo_code())?;
+        }
+        writeln!(f, "}} while a != 0")
+    }
+}
+
+/// Decodes `program` two numbers at a time.
+pub fn disassemble(
+    program: &[u8],
+) -> Result<Disassembly, PuzzleError> {
+    let lines = program
+        .chunks(2)
+        .enumerate()
+        .map(|(index, pair)| match *pair {
+            [opcode, operand] => {
+                Line::decode(index * 2, opcode, operand)
+            }
+            _ => Err(PuzzleError::MissingOperand {
+                pointer: index * 2,
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Disassembly { lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() -> Result<(), PuzzleError> {
+        let disassembly = disassemble(&[
+            2, 4, 1, 3, 7, 5, 4, 1, 5, 5, 0, 3, 3, 0,
+        ])?;
+
+        assert!(disassembly.is_loop());
+        assert_eq!(
+            "  0: bst A   b = a % 8
+  2: bxl 3   b = b ^ 3
+  4: cdv B   c = a >> b
+  6: bxc 1   b = b ^ c
+  8: out B   out(b % 8)
+ 10: adv 3   a = a >> 3
+ 12: jnz 0   if a != 0 goto 0",
+            disassembly.listing()
+        );
+        assert_eq!(
+            "do {
+    b = a % 8
+    b = b ^ 3
+    c = a >> b
+    b = b ^ c
+    out(b % 8)
+    a = a >> 3
+} while a != 0
+",
+            disassembly.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_a_loop() -> Result<(), PuzzleError> {
+        let disassembly = disassemble(&[3, 4, 5, 4, 3, 0])?;
+
+        assert!(!disassembly.is_loop());
+        assert_eq!(
+            "0: if a != 0 goto 4\n2: out(a % 8)\n4: if a != 0 goto 0\n",
+            disassembly.to_string()
+        );
+        assert_eq!(
+            Err(PuzzleError::ReservedComboOperand {
+                pointer: 2
+            }),
+            disassemble(&[0, 3, 5, 7])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("opcode {opcode} at {pointer} is not a 3-bit instruction")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    InvalidOpcode { pointer: usize, opcode: usize },
+    #[error("the instruction at {pointer} has no operand")]
+    #[diagnostic(code(aoc::puzzle::invalid_input))]
+    MissingOperand { pointer: usize },
+    #[error("combo operand 7 of the instruction at {pointer} is reserved")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("combo operand 7 will not appear in valid programs")
+    )]
+    ReservedComboOperand { pointer: usize },
+    #[error(
+        "the program did not halt within {steps} steps"
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help("the program may loop forever, or needs a higher step limit")
+    )]
+    StepLimit { steps: usize },
+    #[error("the program is not a loop over 3-bit chunks of A: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the quine search relies on a single loop that outputs once, shifts A right by 3 and ends in `jnz 0`")
+    )]
+    UnsupportedProgram { reason: String },
+    #[error("no value of register A makes the program output itself")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoQuine,
+}
@@ -1,3 +1,8 @@
+pub mod disasm;
+pub mod error;
 pub mod part1;
 pub mod part1_2;
 pub mod part2;
+pub mod vm;
+
+pub use error::PuzzleError;
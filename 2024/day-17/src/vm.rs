@@ -0,0 +1,485 @@
+//! The 3-bit computer shared by every part: three `u64`
+//! registers, an instruction pointer and a program of 3-bit
+//! numbers, read in opcode/operand pairs.
+
+use std::{collections::HashSet, fmt};
+
+use derive_more::derive::TryFrom;
+
+use crate::PuzzleError;
+
+/// How many instructions [`Vm::run`] executes before giving
+/// up on a program that does not halt, unless changed with
+/// [`Vm::max_steps`].
+pub const DEFAULT_MAX_STEPS: usize = 1_000_000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+#[derive(TryFrom, Debug, Clone, Copy, PartialEq, Eq)]
+#[try_from(repr)]
+#[repr(u8)]
+pub enum Instruction {
+    /// The adv instruction (opcode 0) performs
+    /// division. The numerator is the
+    /// value in the A register. The denominator
+    /// is found by raising 2 to the
+    /// power of the instruction's combo operand.
+    /// (So, an operand of 2 would divide A by
+    /// 4 (2^2); an operand of 5 would divide A by
+    /// 2^B.) The result of the division
+    /// operation is truncated to an integer and
+    /// then written to the A register.
+    Adv = 0,
+    /// The bxl instruction (opcode 1) calculates
+    /// the bitwise XOR of register B
+    /// and the instruction's literal operand,
+    /// then stores the result in register B.
+    Bxl = 1,
+    /// The bst instruction (opcode 2) calculates
+    /// the value of its combo operand modulo
+    /// 8 (thereby keeping only its lowest 3
+    /// bits), then writes that value to the B
+    /// register.
+    Bst = 2,
+    /// The jnz instruction (opcode 3) does
+    /// nothing if the A register is 0.
+    /// However, if the A register is not zero, it
+    /// jumps by setting the instruction
+    /// pointer to the value of its literal
+    /// operand; if this instruction jumps,
+    /// the instruction pointer is not increased
+    /// by 2 after this instruction.
+    Jnz = 3,
+    /// The bxc instruction (opcode 4) calculates
+    /// the bitwise XOR of register B
+    /// and register C, then stores the result in
+    /// register B. (For legacy reasons, this
+    /// instruction reads an operand but ignores
+    /// it.)
+    Bxc = 4,
+    /// The out instruction (opcode 5) calculates
+    /// the value of its combo operand modulo
+    /// 8, then outputs that value. (If a program
+    /// outputs multiple values, they are
+    /// separated by commas.)
+    Out = 5,
+    /// The bdv instruction (opcode 6) works
+    /// exactly like the adv instruction
+    /// except that the result is stored in the B
+    /// register. (The numerator is still read
+    /// from the A register.)
+    Bdv = 6,
+    /// The cdv instruction (opcode 7) works
+    /// exactly like the adv instruction
+    /// except that the result is stored in the C
+    /// register. (The numerator is still read
+    /// from the A register.)
+    Cdv = 7,
+}
+
+/// What a single [`Vm::step`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Continue,
+    Output(u8),
+    /// The pointer is past the end of the program, nothing
+    /// was executed.
+    Halted,
+}
+
+/// Why [`Vm::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// The next instruction is at a breakpoint, `run` again
+    /// to continue from it.
+    Breakpoint(usize),
+}
+
+/// An executed instruction with the registers it started
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub pointer: usize,
+    pub instruction: Instruction,
+    pub operand: u8,
+    pub registers: Registers,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let Registers { a, b, c } = self.registers;
+        write!(
+            f,
+            "{:>3}: {:?} {} (a={a} b={b} c={c})",
+            self.pointer, self.instruction, self.operand
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Vm {
+    pub registers: Registers,
+    pub pointer: usize,
+    program: Vec<u8>,
+    output: Vec<u8>,
+    steps: usize,
+    max_steps: usize,
+    breakpoints: HashSet<usize>,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Vm {
+    pub fn new(
+        registers: Registers,
+        program: Vec<u8>,
+    ) -> Self {
+        Self {
+            registers,
+            pointer: 0,
+            program,
+            output: vec![],
+            steps: 0,
+            max_steps: DEFAULT_MAX_STEPS,
+            breakpoints: HashSet::new(),
+            trace: None,
+        }
+    }
+
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Makes [`Vm::run`] stop before executing the
+    /// instruction at `pointer`.
+    pub fn breakpoint(mut self, pointer: usize) -> Self {
+        self.breakpoints.insert(pointer);
+        self
+    }
+
+    /// Records every executed instruction, see
+    /// [`Vm::trace`].
+    pub fn traced(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Starts the program over from `registers`, keeping
+    /// the breakpoints and the step limit.
+    pub fn reset(&mut self, registers: Registers) {
+        self.registers = registers;
+        self.pointer = 0;
+        self.output.clear();
+        self.steps = 0;
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+
+    /// Everything output since the start or the last
+    /// [`Vm::reset`].
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Instructions executed so far, empty unless the vm is
+    /// [`Vm::traced`].
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    fn combo(
+        &self,
+        pointer: usize,
+        operand: u8,
+    ) -> Result<u64, PuzzleError> {
+        match operand {
+            0..=3 => Ok(operand as u64),
+            4 => Ok(self.registers.a),
+            5 => Ok(self.registers.b),
+            6 => Ok(self.registers.c),
+            _ => Err(PuzzleError::ReservedComboOperand {
+                pointer,
+            }),
+        }
+    }
+
+    /// `A / 2^combo`, which is zero once the power is wider
+    /// than the register.
+    fn divide(
+        &self,
+        pointer: usize,
+        operand: u8,
+    ) -> Result<u64, PuzzleError> {
+        let power = self.combo(pointer, operand)?;
+        Ok(u32::try_from(power)
+            .ok()
+            .and_then(|power| {
+                self.registers.a.checked_shr(power)
+            })
+            .unwrap_or(0))
+    }
+
+    /// Executes the instruction at the pointer.
+    pub fn step(&mut self) -> Result<Step, PuzzleError> {
+        let pointer = self.pointer;
+        let Some(&opcode) = self.program.get(pointer)
+        else {
+            return Ok(Step::Halted);
+        };
+        if self.steps >= self.max_steps {
+            return Err(PuzzleError::StepLimit {
+                steps: self.steps,
+            });
+        }
+        let instruction = Instruction::try_from(opcode)
+            .map_err(|_| PuzzleError::InvalidOpcode {
+                pointer,
+                opcode: opcode as usize,
+            })?;
+        let operand =
+            *self.program.get(pointer + 1).ok_or(
+                PuzzleError::MissingOperand { pointer },
+            )?;
+
+        // The instruction runs on copies, so a failing one
+        // leaves the machine where it was.
+        let mut registers = self.registers;
+        let mut next = pointer + 2;
+        let mut output = None;
+        match instruction {
+            Instruction::Adv => {
+                registers.a =
+                    self.divide(pointer, operand)?
+            }
+            Instruction::Bxl => {
+                registers.b ^= operand as u64
+            }
+            Instruction::Bst => {
+                registers.b =
+                    self.combo(pointer, operand)? % 8
+            }
+            Instruction::Jnz => {
+                if registers.a != 0 {
+                    next = operand as usize;
+                }
+            }
+            Instruction::Bxc => registers.b ^= registers.c,
+            Instruction::Out => {
+                output = Some(
+                    (self.combo(pointer, operand)? % 8)
+                        as u8,
+                );
+            }
+            Instruction::Bdv => {
+                registers.b =
+                    self.divide(pointer, operand)?
+            }
+            Instruction::Cdv => {
+                registers.c =
+                    self.divide(pointer, operand)?
+            }
+        }
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                pointer,
+                instruction,
+                operand,
+                registers: self.registers,
+            });
+        }
+        self.steps += 1;
+        self.pointer = next;
+        self.registers = registers;
+        match output {
+            Some(value) => {
+                self.output.push(value);
+                Ok(Step::Output(value))
+            }
+            None => Ok(Step::Continue),
+        }
+    }
+
+    /// Steps until the program halts or reaches a
+    /// breakpoint. A breakpoint on the instruction `run`
+    /// starts from is passed, so calling it again resumes.
+    pub fn run(&mut self) -> Result<Stop, PuzzleError> {
+        let mut resumed = true;
+        loop {
+            if !resumed
+                && self.breakpoints.contains(&self.pointer)
+            {
+                return Ok(Stop::Breakpoint(self.pointer));
+            }
+            resumed = false;
+            if self.step()? == Step::Halted {
+                return Ok(Stop::Halted);
+            }
+        }
+    }
+
+    /// Steps lazily from one output to the next until the
+    /// program halts, running through breakpoints.
+    pub fn outputs(&mut self) -> Outputs<'_> {
+        Outputs { vm: self }
+    }
+}
+
+pub struct Outputs<'a> {
+    vm: &'a mut Vm,
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = Result<u8, PuzzleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.vm.step() {
+                Ok(Step::Continue) => {}
+                Ok(Step::Output(value)) => {
+                    return Some(Ok(value))
+                }
+                Ok(Step::Halted) => return None,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [u8; 6] = [0, 1, 5, 4, 3, 0];
+
+    fn registers(a: u64) -> Registers {
+        Registers { a, b: 0, c: 0 }
+    }
+
+    #[test]
+    fn test_run() -> Result<(), PuzzleError> {
+        let mut vm =
+            Vm::new(registers(729), EXAMPLE.to_vec());
+        assert_eq!(Stop::Halted, vm.run()?);
+        assert_eq!(
+            &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0],
+            vm.output()
+        );
+
+        vm.reset(registers(729));
+        assert_eq!(
+            vec![4, 6, 3],
+            vm.outputs()
+                .take(3)
+                .collect::<Result<Vec<_>, _>>()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_registers() -> Result<(), PuzzleError> {
+        // bst 4, out 5, adv 3, jnz 0: prints A in octal,
+        // lowest digit first
+        let a = 0o7_1234_5670_1234_5670;
+        let mut vm = Vm::new(
+            registers(a),
+            vec![2, 4, 5, 5, 0, 3, 3, 0],
+        );
+        vm.run()?;
+        assert_eq!(
+            &[
+                0, 7, 6, 5, 4, 3, 2, 1, 0, 7, 6, 5, 4, 3,
+                2, 1, 7
+            ],
+            vm.output()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_breakpoints_and_trace(
+    ) -> Result<(), PuzzleError> {
+        let mut vm =
+            Vm::new(registers(729), EXAMPLE.to_vec())
+                .breakpoint(2)
+                .traced();
+
+        assert_eq!(Stop::Breakpoint(2), vm.run()?);
+        assert_eq!(364, vm.registers.a);
+        assert_eq!(Stop::Breakpoint(2), vm.run()?);
+        assert_eq!(&[4], vm.output());
+
+        let entry = vm.trace()[1];
+        assert_eq!(Instruction::Out, entry.instruction);
+        assert_eq!(
+            "  2: Out 4 (a=364 b=0 c=0)",
+            entry.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut vm =
+            Vm::new(registers(1), vec![3, 0]).max_steps(10);
+        assert_eq!(
+            Err(PuzzleError::StepLimit { steps: 10 }),
+            vm.run()
+        );
+        assert_eq!(
+            Err(PuzzleError::ReservedComboOperand {
+                pointer: 0
+            }),
+            Vm::new(registers(1), vec![5, 7]).run()
+        );
+        assert_eq!(
+            Err(PuzzleError::MissingOperand { pointer: 2 }),
+            Vm::new(registers(1), vec![5, 4, 1]).run()
+        );
+        assert_eq!(
+            Err(PuzzleError::InvalidOpcode {
+                pointer: 0,
+                opcode: 8
+            }),
+            Vm::new(registers(1), vec![8, 0]).run()
+        );
+    }
+
+    #[test]
+    fn test_failed_step_changes_nothing() {
+        // bst 1, then adv with the reserved combo operand
+        let mut vm =
+            Vm::new(registers(8), vec![2, 1, 0, 7])
+                .traced();
+        assert_eq!(Ok(Step::Continue), vm.step());
+        for _ in 0..2 {
+            assert_eq!(
+                Err(PuzzleError::ReservedComboOperand {
+                    pointer: 2
+                }),
+                vm.step()
+            );
+        }
+        assert_eq!(2, vm.pointer);
+        assert_eq!(1, vm.steps);
+        assert_eq!(1, vm.trace().len());
+        assert_eq!(
+            Registers { a: 8, b: 1, c: 0 },
+            vm.registers
+        );
+    }
+}
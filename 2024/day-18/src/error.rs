@@ -0,0 +1,20 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a byte position, like `5,4`")
+    )]
+    InvalidInput { reason: String },
+    #[error("the fallen bytes cut the exit off")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    ExitCutOff,
+    #[error(
+        "the exit stays reachable after every byte fell"
+    )]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NeverBlocked,
+}
@@ -1,2 +1,54 @@
+use glam::IVec2;
+use grid::Grid;
+
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+/// Size of the memory space and how many bytes part 1 lets
+/// fall before looking for a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    /// Largest coordinate on both axes, the exit is at
+    /// `(grid_size, grid_size)`.
+    pub grid_size: u16,
+    pub fallen_bytes: usize,
+}
+
+impl Params {
+    /// The puzzle input's memory space.
+    pub const INPUT: Params = Params {
+        grid_size: 70,
+        fallen_bytes: 1024,
+    };
+    /// The smaller space of the puzzle's example.
+    pub const EXAMPLE: Params = Params {
+        grid_size: 6,
+        fallen_bytes: 12,
+    };
+
+    fn exit(&self) -> IVec2 {
+        IVec2::splat(self.grid_size.into())
+    }
+
+    /// The memory space holding the index of the first byte
+    /// to fall on each cell, `usize::MAX` where none does.
+    fn fall_times(&self, bytes: &[IVec2]) -> Grid<usize> {
+        let size = usize::from(self.grid_size) + 1;
+        let mut grid = Grid::new(size, size, usize::MAX);
+        for (time, byte) in bytes.iter().enumerate().rev() {
+            if let Some(cell) = grid.get_mut(*byte) {
+                *cell = time;
+            }
+        }
+        grid
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::INPUT
+    }
+}
+
+pub use error::PuzzleError;
@@ -1,6 +1,5 @@
-use std::ops::Not;
-
 use glam::IVec2;
+use grid::path::GridGraph;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -9,67 +8,38 @@ use nom::{
     IResult, Parser,
 };
 
-use pathfinding::prelude::*;
-
-const GRID_SIZE: IVec2 = if cfg!(test) {
-    IVec2::splat(6)
-} else {
-    IVec2::splat(70)
-};
-
-const DIRECTIONS: [IVec2; 4] =
-    [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+use crate::{Params, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
+
+pub fn process_with(
+    input: &str,
+    params: Params,
+) -> miette::Result<String> {
     let input = input.trim();
     let (_input, falling_bytes) =
         parse(input).map_err(|e| {
-            miette::miette!("parsing failed {}", e)
+            PuzzleError::InvalidInput {
+                reason: e.to_string(),
+            }
         })?;
 
-    let end = falling_bytes.len().min(if cfg!(test) {
-        12
-    } else {
-        1024
-    });
-
-    let start_node = IVec2::ZERO;
-    let mut positions_visited = vec![];
-    let result = dijkstra(
-        &start_node,
-        |position| {
-            DIRECTIONS
-                .iter()
-                .filter_map(|dir| {
-                    let next_pos = position + dir;
-
-                    if !((0..=GRID_SIZE.x)
-                        .contains(&next_pos.x)
-                        && (0..=GRID_SIZE.y)
-                            .contains(&next_pos.y))
-                    {
-                        return None;
-                    }
+    let end = falling_bytes.len().min(params.fallen_bytes);
+    let fall_times = params.fall_times(&falling_bytes);
 
-                    if falling_bytes[0..end]
-                        .contains(&next_pos)
-                        .not()
-                    {
-                        positions_visited.push(next_pos);
-                        Some((next_pos, 1usize))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        },
-        |&p| p == GRID_SIZE,
-    );
+    let (_path, steps) =
+        GridGraph::new(&fall_times, |_, &fell| fell >= end)
+            .shortest_path(IVec2::ZERO, |&p| {
+                p == params.exit()
+            })
+            .ok_or(PuzzleError::ExitCutOff)?;
 
-    Ok(result.unwrap().1.to_string())
+    Ok(steps.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     separated_list1(
         line_ending,
         separated_pair(
@@ -112,7 +82,10 @@ mod tests {
 0,5
 1,6
 2,0";
-        assert_eq!("22", process(input)?);
+        assert_eq!(
+            "22",
+            process_with(input, Params::EXAMPLE)?
+        );
         Ok(())
     }
 }
@@ -1,6 +1,5 @@
-use std::ops::Not;
-
 use glam::IVec2;
+use grid::path::GridGraph;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -9,77 +8,59 @@ use nom::{
     IResult, Parser,
 };
 
-use pathfinding::prelude::*;
-
-const GRID_SIZE: IVec2 = if cfg!(test) {
-    IVec2::splat(6)
-} else {
-    IVec2::splat(70)
-};
-
-const DIRECTIONS: [IVec2; 4] =
-    [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+use crate::{Params, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
+
+pub fn process_with(
+    input: &str,
+    params: Params,
+) -> miette::Result<String> {
     let input = input.trim();
     let (_input, falling_bytes) =
         parse(input).map_err(|e| {
-            miette::miette!("parsing failed {}", e)
+            PuzzleError::InvalidInput {
+                reason: e.to_string(),
+            }
         })?;
 
-    let mut lower = (GRID_SIZE - 2).x as usize;
-    let mut upper = falling_bytes.len();
-
-    let result = loop {
-        let n = lower + (upper - lower) / 2;
-        let end = falling_bytes.len().min(n);
-        let start_node = IVec2::ZERO;
-
-        let path = dijkstra(
-            &start_node,
-            |position| {
-                DIRECTIONS
-                    .iter()
-                    .filter_map(|dir| {
-                        let next_pos = position + dir;
-
-                        if !((0..=GRID_SIZE.x)
-                            .contains(&next_pos.x)
-                            && (0..=GRID_SIZE.y)
-                                .contains(&next_pos.y))
-                        {
-                            return None;
-                        }
+    let fall_times = params.fall_times(&falling_bytes);
+    let blocked_after = |n: usize| {
+        GridGraph::new(&fall_times, |_, &fell| fell >= n)
+            .shortest_path(IVec2::ZERO, |&p| {
+                p == params.exit()
+            })
+            .is_none()
+    };
 
-                        if falling_bytes[0..end]
-                            .contains(&next_pos)
-                            .not()
-                        {
-                            Some((next_pos, 1usize))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            },
-            |&p| p == GRID_SIZE,
-        );
+    if !blocked_after(falling_bytes.len()) {
+        return Err(PuzzleError::NeverBlocked.into());
+    }
 
-        match path {
-            Some(_) => lower = n + 1,
-            None => {
-                if n == lower {
-                    break &falling_bytes[n - 1];
-                }
-                upper = n;
-            }
+    // The fewest fallen bytes that block the exit, the last
+    // of them is the one to blame.
+    let mut lower = 0;
+    let mut upper = falling_bytes.len();
+    while lower < upper {
+        let n = lower + (upper - lower) / 2;
+        if blocked_after(n) {
+            upper = n;
+        } else {
+            lower = n + 1;
         }
-    };
+    }
+
+    let result = upper
+        .checked_sub(1)
+        .map(|last| falling_bytes[last])
+        .ok_or(PuzzleError::ExitCutOff)?;
 
     Ok(format!("{},{}", result.x, result.y))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     separated_list1(
         line_ending,
         separated_pair(
@@ -122,7 +103,43 @@ mod tests {
 0,5
 1,6
 2,0";
-        assert_eq!("6,1", process(input)?);
+        assert_eq!(
+            "6,1",
+            process_with(input, Params::EXAMPLE)?
+        );
         Ok(())
     }
+
+    #[test]
+    fn test_first_byte_blocks() -> miette::Result<()> {
+        let params = Params {
+            grid_size: 1,
+            fallen_bytes: 0,
+        };
+        assert_eq!(
+            "1,1",
+            process_with("1,1\n1,0", params)?
+        );
+        assert_eq!(
+            "0,1",
+            process_with("1,0\n0,1\n1,1", params)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_never_blocked() {
+        let error = process_with(
+            "1,0\n3,3",
+            Params {
+                grid_size: 1,
+                fallen_bytes: 0,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::NeverBlocked),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
 }
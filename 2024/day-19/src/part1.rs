@@ -10,10 +10,12 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let (_input, (towels, designs)) = parse(input)
-        .map_err(|e| {
-            miette::miette!("parse failed {}", e)
+        .map_err(|e| PuzzleError::InvalidInput {
+            reason: e.to_string(),
         })?;
 
     // dbg!(towels, designs);
@@ -57,7 +59,7 @@ fn validate_design(
     result
 }
 
-fn parse(
+pub fn parse(
     input: &str,
 ) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     separated_pair(
@@ -10,10 +10,12 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let (_input, (towels, designs)) = parse(input)
-        .map_err(|e| {
-            miette::miette!("parse failed {}", e)
+        .map_err(|e| PuzzleError::InvalidInput {
+            reason: e.to_string(),
         })?;
 
     let count: usize = designs
@@ -58,7 +60,7 @@ fn count_valid_designs(
     result
 }
 
-fn parse(
+pub fn parse(
     input: &str,
 ) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     separated_pair(
@@ -0,0 +1,19 @@
+use grid::GridError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Map(#[from] GridError),
+    #[error("the map has no `{tile}` tile")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("the race starts on `S` and ends on `E`")
+    )]
+    MissingTile { tile: char },
+    #[error("no path leads from the start to the end")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoPath,
+}
@@ -1,2 +1,22 @@
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
+
+/// How much time a cheat has to save to be counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub min_savings: usize,
+}
+
+impl Params {
+    /// The threshold the puzzle asks about for the input.
+    pub const INPUT: Params = Params { min_savings: 100 };
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::INPUT
+    }
+}
@@ -1,154 +1,81 @@
-use std::{collections::HashSet, ops::Not};
-
 use glam::IVec2;
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::{all_consuming, opt},
-    multi::{many1, separated_list1},
-    IResult,
-};
-use nom_locate::LocatedSpan;
-use pathfinding::prelude::*;
+use grid::{path::GridGraph, Grid};
 
-const DIRECTIONS: [IVec2; 4] =
-    [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+use crate::{Params, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let input = input.trim();
-    let (_input, Map { start, end, walls }) =
-        all_consuming(parse)(Span::new(input)).map_err(
-            |e| miette::miette!("parsing failed {}", e),
-        )?;
+    process_with(input, Params::default())
+}
 
-    let first_run = dijkstra(
-        &start,
-        |position| {
-            DIRECTIONS
-                .iter()
-                .filter_map(|dir| {
-                    let next_pos = position + dir;
-                    walls
-                        .contains(&next_pos)
-                        .not()
-                        .then_some((next_pos, 1))
-                })
-                .collect::<Vec<_>>()
-        },
-        |&pos| pos == end,
-    )
-    .expect("a valid path");
+pub fn process_with(
+    input: &str,
+    params: Params,
+) -> miette::Result<String> {
+    let input = input.trim();
+    let map = parse(input)?;
 
-    let x_max =
-        walls.iter().map(|pos| pos.x).max().unwrap();
-    let y_max =
-        walls.iter().map(|pos| pos.y).max().unwrap();
+    let (_path, orig_cost) =
+        GridGraph::new(&map.walls, |_, wall| !wall)
+            .shortest_path(map.start, |&pos| pos == map.end)
+            .ok_or(PuzzleError::NoPath)?;
 
-    let result = walls
+    let result = map
+        .walls
         .iter()
+        .filter_map(|(pos, wall)| wall.then_some(pos))
         .filter(|wall| {
-            DIRECTIONS
-                .iter()
-                .filter(|dir| {
-                    let next_pos = **wall + **dir;
-                    (0..x_max).contains(&next_pos.x)
-                        && (0..y_max).contains(&next_pos.y)
-                        && walls.contains(&next_pos).not()
-                })
+            map.walls
+                .neighbors4(*wall)
+                .filter(|next_pos| !map.walls[*next_pos])
                 .count()
                 >= 2
         })
-        .filter_map(|wall| {
-            dijkstra(
-                &start,
-                |position| {
-                    DIRECTIONS
-                        .iter()
-                        .filter_map(|dir| {
-                            let next_pos = position + dir;
-                            (next_pos == *wall
-                                || walls
-                                    .contains(&next_pos)
-                                    .not())
-                            .then_some((next_pos, 1))
-                        })
-                        .collect::<Vec<_>>()
-                },
-                |&pos| pos == end,
-            )
+        .filter_map(|cheat| {
+            GridGraph::new(&map.walls, |pos, wall| {
+                pos == cheat || !wall
+            })
+            .shortest_path(map.start, |&pos| pos == map.end)
             .map(|(_path, cost)| cost)
         })
-        .map(|cost| first_run.1 - cost)
-        .filter(|savings| savings >= &100)
+        .map(|cost| orig_cost - cost)
+        .filter(|&savings| savings >= params.min_savings)
         .count();
 
     Ok(result.to_string())
 }
 
-type Span<'a> = LocatedSpan<&'a str>;
-
-fn token(input: Span) -> IResult<Span, (IVec2, char)> {
-    let y = input.location_line();
-    let x = input.get_column();
-    let (input, token) = one_of(".#SE")(input)?;
-
-    Ok((
-        input,
-        (
-            IVec2::new(x as i32 - 1, y as i32 - 1),
-            token,
-        ),
-    ))
-}
-
-struct Map {
-    start: IVec2,
-    end: IVec2,
-    walls: HashSet<IVec2>,
+pub struct Map {
+    pub start: IVec2,
+    pub end: IVec2,
+    pub walls: Grid<bool>,
 }
 
-fn parse(input: Span) -> IResult<Span, Map> {
-    let (input, items) =
-        separated_list1(line_ending, many1(token))(input)?;
-
-    let (input, _) = opt(line_ending)(input)?;
-
-    let (starting_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'S')
-        .cloned()
-        .expect("should have a starting position");
-    let (ending_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'E')
-        .cloned()
-        .expect("should have a ending position");
-    let walls = items
-        .into_iter()
-        .flatten()
-        .filter_map(|(pos, val)| {
-            (val == '#').then_some(pos)
-        })
-        .collect::<HashSet<IVec2>>();
-
-    Ok((
-        input,
-        Map {
-            start: starting_position,
-            end: ending_position,
-            walls,
-        },
-    ))
+pub fn parse(input: &str) -> Result<Map, PuzzleError> {
+    let grid = Grid::try_parse(input, |c| {
+        ".#SE".contains(c).then_some(c)
+    })?;
+
+    let start = grid
+        .find(|c| c == &'S')
+        .ok_or(PuzzleError::MissingTile { tile: 'S' })?;
+    let end = grid
+        .find(|c| c == &'E')
+        .ok_or(PuzzleError::MissingTile { tile: 'E' })?;
+
+    Ok(Map {
+        start,
+        end,
+        walls: grid.map(|c| c == &'#'),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "###############
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "###############
 #...#...#.....#
 #.#.#.#.#.###.#
 #S#...#.#.#...#
@@ -163,7 +90,27 @@ mod tests {
 #.#.#.#.#.#.###
 #...#...#...###
 ###############";
-        assert_eq!("", process(input)?);
+
+    #[rstest]
+    #[case(64, "1")]
+    #[case(40, "2")]
+    #[case(38, "3")]
+    #[case(36, "4")]
+    #[case(20, "5")]
+    #[case(12, "8")]
+    #[case(10, "10")]
+    #[case(8, "14")]
+    #[case(6, "16")]
+    #[case(4, "30")]
+    #[case(2, "44")]
+    fn test_process(
+        #[case] min_savings: usize,
+        #[case] count: &str,
+    ) -> miette::Result<()> {
+        assert_eq!(
+            count,
+            process_with(EXAMPLE, Params { min_savings })?
+        );
         Ok(())
     }
 }
@@ -1,43 +1,23 @@
-use std::{collections::HashSet, ops::Not};
-
-use glam::IVec2;
+use grid::path::GridGraph;
 use itertools::Itertools;
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::{all_consuming, opt},
-    multi::{many1, separated_list1},
-    IResult,
-};
-use nom_locate::LocatedSpan;
-use pathfinding::prelude::*;
 
-const DIRECTIONS: [IVec2; 4] =
-    [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+use crate::{part1::parse, Params, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
+    process_with(input, Params::default())
+}
+
+pub fn process_with(
+    input: &str,
+    params: Params,
+) -> miette::Result<String> {
     let input = input.trim();
-    let (_input, Map { start, end, walls }) =
-        all_consuming(parse)(Span::new(input)).map_err(
-            |e| miette::miette!("parsing failed {}", e),
-        )?;
+    let map = parse(input)?;
 
-    let (orig_path, orig_cost) = dijkstra(
-        &start,
-        |position| {
-            DIRECTIONS
-                .iter()
-                .filter_map(|dir| {
-                    let next_pos = position + dir;
-                    walls
-                        .contains(&next_pos)
-                        .not()
-                        .then_some((next_pos, 1))
-                })
-                .collect::<Vec<_>>()
-        },
-        |&pos| pos == end,
-    )
-    .expect("a valid path");
+    let (orig_path, orig_cost) =
+        GridGraph::new(&map.walls, |_, wall| !wall)
+            .shortest_path(map.start, |&pos| pos == map.end)
+            .ok_or(PuzzleError::NoPath)?;
 
     let result = orig_path
         .iter()
@@ -61,77 +41,19 @@ pub fn process(input: &str) -> miette::Result<String> {
                 Some(orig_cost - cheat_cost)
             },
         )
-        .filter(|savings| savings >= &100)
+        .filter(|&savings| savings >= params.min_savings)
         .count();
 
     Ok(result.to_string())
 }
 
-type Span<'a> = LocatedSpan<&'a str>;
-
-fn token(input: Span) -> IResult<Span, (IVec2, char)> {
-    let y = input.location_line();
-    let x = input.get_column();
-    let (input, token) = one_of(".#SE")(input)?;
-
-    Ok((
-        input,
-        (
-            IVec2::new(x as i32 - 1, y as i32 - 1),
-            token,
-        ),
-    ))
-}
-
-struct Map {
-    start: IVec2,
-    end: IVec2,
-    walls: HashSet<IVec2>,
-}
-
-fn parse(input: Span) -> IResult<Span, Map> {
-    let (input, items) =
-        separated_list1(line_ending, many1(token))(input)?;
-
-    let (input, _) = opt(line_ending)(input)?;
-
-    let (starting_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'S')
-        .cloned()
-        .expect("should have a starting position");
-    let (ending_position, _) = items
-        .iter()
-        .flatten()
-        .find(|(_, val)| val == &'E')
-        .cloned()
-        .expect("should have a ending position");
-    let walls = items
-        .into_iter()
-        .flatten()
-        .filter_map(|(pos, val)| {
-            (val == '#').then_some(pos)
-        })
-        .collect::<HashSet<IVec2>>();
-
-    Ok((
-        input,
-        Map {
-            start: starting_position,
-            end: ending_position,
-            walls,
-        },
-    ))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "###############
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "###############
 #...#...#.....#
 #.#.#.#.#.###.#
 #S#...#.#.#...#
@@ -146,7 +68,21 @@ mod tests {
 #.#.#.#.#.#.###
 #...#...#...###
 ###############";
-        assert_eq!("", process(input)?);
+
+    #[rstest]
+    #[case(76, "3")]
+    #[case(74, "7")]
+    #[case(72, "29")]
+    #[case(70, "41")]
+    #[case(50, "285")]
+    fn test_process(
+        #[case] min_savings: usize,
+        #[case] count: &str,
+    ) -> miette::Result<()> {
+        assert_eq!(
+            count,
+            process_with(EXAMPLE, Params { min_savings })?
+        );
         Ok(())
     }
 }
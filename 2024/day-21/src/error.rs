@@ -0,0 +1,14 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("line {line} is not a door code: `{code}`")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help(
+            "a code is digits followed by `A`, like `029A`"
+        )
+    )]
+    InvalidCode { line: usize, code: String },
+}
@@ -4,19 +4,19 @@ use cgmath::Vector2;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 
+use crate::PuzzleError;
+
 type Vec2 = Vector2<isize>;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let codes: Vec<_> = input.lines().collect();
+    let codes = parse(input)?;
 
     let result: usize = codes
         .iter()
-        .map(|code| {
+        .map(|&(code, numeric_val)| {
             let len = get_numpad_code_len(code, 2);
-            let numeric_val: usize =
-                code[..(code.len() - 1)].parse().unwrap();
             len * numeric_val
         })
         .sum();
@@ -24,6 +24,30 @@ pub fn process(input: &str) -> miette::Result<String> {
     Ok(result.to_string())
 }
 
+/// Each door code with its numeric part.
+pub fn parse(
+    input: &str,
+) -> Result<Vec<(&str, usize)>, PuzzleError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, code)| {
+            code.strip_suffix('A')
+                .filter(|digits| {
+                    digits
+                        .chars()
+                        .all(|c| c.is_ascii_digit())
+                })
+                .and_then(|digits| digits.parse().ok())
+                .map(|numeric_val| (code, numeric_val))
+                .ok_or_else(|| PuzzleError::InvalidCode {
+                    line: i + 1,
+                    code: code.to_string(),
+                })
+        })
+        .collect()
+}
+
 fn get_numpad_code_len(code: &str, depth: usize) -> usize {
     let mut chars: Vec<char> = code.chars().collect();
     chars.insert(0, 'A');
@@ -95,6 +119,10 @@ static NUMERIC_SHORTEST_PATH: Lazy<
     let keys: Vec<char> = NUMPAD.keys().copied().collect();
 
     let mut paths = HashMap::new();
+    // Repeating a digit only takes another press of `A`.
+    for &key in &keys {
+        paths.insert((key, key), vec![String::new()]);
+    }
 
     keys.iter().tuple_combinations().for_each(
         |(&from, &to)| {
@@ -270,4 +298,31 @@ mod tests {
         assert_eq!("126384", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_repeated_digit() {
+        // Every robot already points at `A` after the first
+        // `0`, so the second one takes a single extra press.
+        assert_eq!(
+            get_numpad_code_len("10A", 2) + 1,
+            get_numpad_code_len("100A", 2)
+        );
+    }
+
+    #[test]
+    fn test_invalid_codes() {
+        for (input, line, code) in [
+            ("029A\n\n980A", 2, ""),
+            ("02B9A", 1, "02B9A"),
+            ("029", 1, "029"),
+        ] {
+            assert_eq!(
+                Err(PuzzleError::InvalidCode {
+                    line,
+                    code: code.to_string(),
+                }),
+                parse(input)
+            );
+        }
+    }
 }
@@ -4,23 +4,23 @@ use cgmath::Vector2;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 
+use crate::part1::parse;
+
 type Vec2 = Vector2<isize>;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
 
-    let codes: Vec<_> = input.lines().collect();
+    let codes = parse(input)?;
 
     let result: usize = codes
         .iter()
-        .map(|code| {
+        .map(|&(code, numeric_val)| {
             let len = get_numpad_code_len(
                 code,
                 25,
                 &mut HashMap::new(),
             );
-            let numeric_val: usize =
-                code[..(code.len() - 1)].parse().unwrap();
             len * numeric_val
         })
         .sum();
@@ -115,6 +115,10 @@ static NUMERIC_SHORTEST_PATH: Lazy<
     let keys: Vec<char> = NUMPAD.keys().copied().collect();
 
     let mut paths = HashMap::new();
+    // Repeating a digit only takes another press of `A`.
+    for &key in &keys {
+        paths.insert((key, key), vec![String::new()]);
+    }
 
     keys.iter().tuple_combinations().for_each(
         |(&from, &to)| {
@@ -287,7 +291,7 @@ mod tests {
 179A
 456A
 379A";
-        assert_eq!("126384", process(input)?);
+        assert_eq!("154115708116294", process(input)?);
         Ok(())
     }
 }
@@ -0,0 +1,17 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("`{text}` is not a secret number")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help(
+            "every line is a buyer's initial secret number"
+        )
+    )]
+    InvalidSecret { text: String },
+    #[error("there are no buyers to sell to")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoBuyers,
+}
@@ -1,26 +1,33 @@
 use std::iter::successors;
 
-use miette::IntoDiagnostic;
+use crate::PuzzleError;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
     let result: usize = input
         .lines()
         .map(|line| {
-            process_secret(line).unwrap().nth(2000).unwrap()
+            Ok(process_secret(line)?
+                .nth(2000)
+                .expect("secrets should go on forever"))
         })
-        .sum();
+        .sum::<Result<_, PuzzleError>>()?;
 
     Ok(result.to_string())
 }
 fn process_secret(
     secret: &str,
-) -> miette::Result<impl Iterator<Item = usize>> {
-    let secret =
-        secret.parse::<usize>().into_diagnostic()?;
+) -> Result<impl Iterator<Item = usize>, PuzzleError> {
+    let secret = secret.parse::<usize>().map_err(|_| {
+        PuzzleError::InvalidSecret {
+            text: secret.to_string(),
+        }
+    })?;
 
     Ok(successors(Some(secret), |secret| {
-        let value = secret * 64;
+        // Pruning keeps only the low bits, which wrapping
+        // leaves intact for oversized initial secrets.
+        let value = secret.wrapping_mul(64);
         let secret = prune(mix(*secret, value));
 
         let value = secret / 32;
@@ -74,4 +81,14 @@ mod tests {
     fn test_prune() {
         assert_eq!(16113920, prune(100000000));
     }
+
+    #[test]
+    fn test_invalid_secret() {
+        assert_eq!(
+            Some(PuzzleError::InvalidSecret {
+                text: "-10".to_string()
+            }),
+            process_secret("-10").err()
+        );
+    }
 }
@@ -1,15 +1,15 @@
 use std::{collections::HashMap, iter::successors};
 
 use itertools::Itertools;
-use miette::IntoDiagnostic;
+
+use crate::PuzzleError;
 
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
-    let hashmap = input.lines().fold(
+    let hashmap = input.lines().try_fold(
         HashMap::<[i32; 4], usize>::new(),
         |mut map, line| {
-            let inner_map = cost_and_change(line)
-                .unwrap()
+            let inner_map = cost_and_change(line)?
                 .take(2000)
                 .tuple_windows()
                 .fold(
@@ -28,20 +28,23 @@ pub fn process(input: &str) -> miette::Result<String> {
                     })
                 .or_insert(inner_value);
             }
-            map
+            Ok::<_, PuzzleError>(map)
         },
-    );
+    )?;
 
-    let result: &usize = hashmap.values().max().unwrap();
+    let result: &usize = hashmap
+        .values()
+        .max()
+        .ok_or(PuzzleError::NoBuyers)?;
 
     Ok(result.to_string())
 }
 
 fn cost_and_change(
     secret: &str,
-) -> miette::Result<impl Iterator<Item = (usize, i32)>> {
-    Ok(process_secret(secret)
-        .unwrap()
+) -> Result<impl Iterator<Item = (usize, i32)>, PuzzleError>
+{
+    Ok(process_secret(secret)?
         .map(|num| num % 10)
         .tuple_windows()
         .map(|(a, b)| (b, b as i32 - a as i32)))
@@ -49,12 +52,17 @@ fn cost_and_change(
 
 fn process_secret(
     secret: &str,
-) -> miette::Result<impl Iterator<Item = usize>> {
-    let secret =
-        secret.parse::<usize>().into_diagnostic()?;
+) -> Result<impl Iterator<Item = usize>, PuzzleError> {
+    let secret = secret.parse::<usize>().map_err(|_| {
+        PuzzleError::InvalidSecret {
+            text: secret.to_string(),
+        }
+    })?;
 
     Ok(successors(Some(secret), |secret| {
-        let value = secret * 64;
+        // Pruning keeps only the low bits, which wrapping
+        // leaves intact for oversized initial secrets.
+        let value = secret.wrapping_mul(64);
         let secret = prune(mix(*secret, value));
 
         let value = secret / 32;
@@ -109,6 +117,15 @@ mod tests {
         assert_eq!(16113920, prune(100000000));
     }
 
+    #[test]
+    fn test_no_buyers() {
+        let error = process("").unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::NoBuyers),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
+
     #[test]
     fn test_cost_and_change() {
         let input = "123";
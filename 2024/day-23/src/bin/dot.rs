@@ -0,0 +1,46 @@
+use aoc_input::Args;
+use day_23::{
+    clique::maximum_cliques, dot::to_dot, part2,
+    PuzzleError,
+};
+use petgraph::prelude::UnGraphMap;
+
+/// Writes the input's network as a GraphViz graph next to
+/// the input, with a largest LAN party filled in, or the
+/// computers of `--highlight kh,tc,qp`.
+fn main() -> miette::Result<()> {
+    let args = Args::with_options(
+        std::env::args().skip(1),
+        env!("CARGO_MANIFEST_DIR"),
+        &["--highlight"],
+    )?;
+    let source = args.source;
+    let file = source.read()?;
+    let (_input, edges) = part2::parse(file.trim())
+        .map_err(|e| PuzzleError::InvalidInput {
+            reason: e.to_string(),
+        })?;
+    let graph = UnGraphMap::<&str, ()>::from_edges(&edges);
+
+    let clique = match &args.highlight {
+        Some(computers) => {
+            computers.iter().map(String::as_str).collect()
+        }
+        None => maximum_cliques(&graph)
+            .into_iter()
+            .next()
+            .unwrap_or_default(),
+    };
+
+    let path = source.output_path("dot");
+    std::fs::write(&path, to_dot(&graph, &clique))
+        .map_err(|e| {
+            miette::miette!(
+                "writing {} failed {}",
+                path.display(),
+                e
+            )
+        })?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//! Groups of computers that are all connected to each
+//! other, in any network of the puzzle's format.
+//!
+//! Maximal cliques come from Bron–Kerbosch with pivoting:
+//! a clique is grown one node at a time from the candidates
+//! connected to all of it, skipping the neighbours of a
+//! pivot because every clique they are in is found through
+//! the pivot or one of its non-neighbours anyway.
+
+use std::collections::{HashMap, HashSet};
+
+use petgraph::graphmap::{NodeTrait, UnGraphMap};
+
+/// Every clique that cannot be extended by another node,
+/// each sorted and in no particular order. A graph without
+/// nodes has none.
+pub fn maximal_cliques<N: NodeTrait, E>(
+    graph: &UnGraphMap<N, E>,
+) -> Vec<Vec<N>> {
+    let mut cliques = vec![];
+    if graph.node_count() == 0 {
+        return cliques;
+    }
+    // Self-loops are no connection to another computer.
+    let adjacency = graph
+        .nodes()
+        .map(|node| {
+            let neighbours = graph
+                .neighbors(node)
+                .filter(|&other| other != node)
+                .collect::<HashSet<_>>();
+            (node, neighbours)
+        })
+        .collect::<HashMap<_, _>>();
+    bron_kerbosch(
+        &adjacency,
+        &mut vec![],
+        graph.nodes().collect(),
+        HashSet::new(),
+        &mut cliques,
+    );
+    cliques
+}
+
+/// The largest cliques, sorted. More than one means the
+/// maximum is not unique.
+pub fn maximum_cliques<N: NodeTrait, E>(
+    graph: &UnGraphMap<N, E>,
+) -> Vec<Vec<N>> {
+    let mut cliques = maximal_cliques(graph);
+    let size =
+        cliques.iter().map(Vec::len).max().unwrap_or(0);
+    cliques.retain(|clique| clique.len() == size);
+    cliques.sort();
+    cliques
+}
+
+/// Reports every maximal clique that extends `clique` with
+/// nodes of `candidates`, but none of `excluded`, which
+/// were already tried.
+fn bron_kerbosch<N: NodeTrait>(
+    adjacency: &HashMap<N, HashSet<N>>,
+    clique: &mut Vec<N>,
+    mut candidates: HashSet<N>,
+    mut excluded: HashSet<N>,
+    cliques: &mut Vec<Vec<N>>,
+) {
+    let Some(pivot) = candidates
+        .union(&excluded)
+        .max_by_key(|&node| {
+            adjacency[node]
+                .intersection(&candidates)
+                .count()
+        })
+        .copied()
+    else {
+        let mut found = clique.clone();
+        found.sort();
+        cliques.push(found);
+        return;
+    };
+
+    let branches = candidates
+        .difference(&adjacency[&pivot])
+        .copied()
+        .collect::<Vec<_>>();
+    for node in branches {
+        let node_neighbours = &adjacency[&node];
+        clique.push(node);
+        bron_kerbosch(
+            adjacency,
+            clique,
+            candidates
+                .intersection(node_neighbours)
+                .copied()
+                .collect(),
+            excluded
+                .intersection(node_neighbours)
+                .copied()
+                .collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+/// Sets of three computers connected to each other where
+/// at least one of them satisfies `predicate`.
+pub fn count_triangles<N: NodeTrait, E>(
+    graph: &UnGraphMap<N, E>,
+    predicate: impl Fn(N) -> bool,
+) -> usize {
+    // Only counting `a < b < c` sees every triangle once.
+    graph
+        .nodes()
+        .flat_map(|a| {
+            graph
+                .neighbors(a)
+                .filter(move |&b| a < b)
+                .map(move |b| (a, b))
+        })
+        .flat_map(|(a, b)| {
+            graph
+                .neighbors(b)
+                .filter(move |&c| b < c)
+                .map(move |c| (a, b, c))
+        })
+        .filter(|&(a, b, c)| {
+            graph.contains_edge(a, c)
+                && [a, b, c].into_iter().any(&predicate)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::part2::parse;
+
+    const EXAMPLE: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+
+    #[test]
+    fn test_example() {
+        let (_, edges) = parse(EXAMPLE).unwrap();
+        let graph =
+            UnGraphMap::<&str, ()>::from_edges(&edges);
+
+        assert_eq!(
+            vec![vec!["co", "de", "ka", "ta"]],
+            maximum_cliques(&graph)
+        );
+        assert_eq!(12, count_triangles(&graph, |_| true));
+        assert_eq!(
+            7,
+            count_triangles(&graph, |node| node
+                .starts_with('t'))
+        );
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        // Two triangles sharing an edge, and a loose pair.
+        let graph = UnGraphMap::<u8, ()>::from_edges([
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (2, 4),
+            (3, 4),
+            (5, 6),
+        ]);
+
+        let mut cliques = maximal_cliques(&graph);
+        cliques.sort();
+        assert_eq!(
+            vec![vec![1, 2, 3], vec![2, 3, 4], vec![5, 6]],
+            cliques
+        );
+        assert_eq!(
+            vec![vec![1, 2, 3], vec![2, 3, 4]],
+            maximum_cliques(&graph)
+        );
+        assert_eq!(
+            1,
+            count_triangles(&graph, |node| node == 4)
+        );
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = UnGraphMap::<u8, ()>::new();
+
+        assert_eq!(
+            Vec::<Vec<u8>>::new(),
+            maximal_cliques(&graph)
+        );
+        assert_eq!(
+            Vec::<Vec<u8>>::new(),
+            maximum_cliques(&graph)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! GraphViz export of the network, for looking at how a LAN
+//! party sits in it.
+
+use petgraph::{
+    dot::{Config, Dot},
+    prelude::UnGraphMap,
+};
+
+/// The network as a GraphViz `graph`, with the computers
+/// of `clique` filled in and the connections between them
+/// drawn in red.
+pub fn to_dot(
+    graph: &UnGraphMap<&str, ()>,
+    clique: &[&str],
+) -> String {
+    let in_clique = |node: &str| clique.contains(&node);
+    format!(
+        "{:?}",
+        Dot::with_attr_getters(
+            graph,
+            &[Config::NodeNoLabel, Config::EdgeNoLabel],
+            &|_, (a, b, _)| {
+                if in_clique(a) && in_clique(b) {
+                    "color = red, penwidth = 2".to_string()
+                } else {
+                    String::new()
+                }
+            },
+            &|_, (node, _)| {
+                let label = format!("label = \"{node}\"");
+                if in_clique(node) {
+                    format!("{label}, style = filled, fillcolor = gold")
+                } else {
+                    label
+                }
+            },
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let graph = UnGraphMap::from_edges([
+            ("ka", "co"),
+            ("co", "de"),
+            ("de", "ka"),
+            ("ka", "tb"),
+        ]);
+
+        assert_eq!(
+            r#"graph {
+    0 [ label = "ka", style = filled, fillcolor = gold]
+    1 [ label = "co", style = filled, fillcolor = gold]
+    2 [ label = "de", style = filled, fillcolor = gold]
+    3 [ label = "tb"]
+    1 -- 0 [ color = red, penwidth = 2]
+    1 -- 2 [ color = red, penwidth = 2]
+    2 -- 0 [ color = red, penwidth = 2]
+    0 -- 3 [ ]
+}
+"#,
+            to_dot(&graph, &["co", "de", "ka"])
+        );
+    }
+}
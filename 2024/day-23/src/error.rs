@@ -0,0 +1,27 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("every line is a connection, like `kh-tc`")
+    )]
+    InvalidInput { reason: String },
+    #[error("no computers are connected to each other")]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    NoLanParty,
+    #[error(
+        "{} groups of {size} connected computers, the password is ambiguous",
+        passwords.len()
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::ambiguous),
+        help("the candidates are {passwords:?}")
+    )]
+    AmbiguousLanParty {
+        size: usize,
+        passwords: Vec<String>,
+    },
+}
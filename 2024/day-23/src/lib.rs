@@ -1,2 +1,7 @@
+pub mod clique;
+pub mod dot;
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, line_ending},
@@ -8,38 +7,27 @@ use nom::{
 };
 use petgraph::prelude::UnGraphMap;
 
+use crate::{clique::count_triangles, PuzzleError};
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
     let (_input, edges) = parse(input).map_err(|e| {
-        miette::miette!("parsing failed {}", e)
+        PuzzleError::InvalidInput {
+            reason: e.to_string(),
+        }
     })?;
 
     let g = &UnGraphMap::<&str, ()>::from_edges(&edges);
 
-    let result = g
-        .nodes()
-        .flat_map(|node| {
-            g.neighbors(node)
-                .tuple_combinations()
-                .filter(move |(a, b)| {
-                    g.contains_edge(a, b)
-                        && [node, a, b]
-                            .iter()
-                            .any(|n| n.starts_with("t"))
-                })
-                .map(move |(a, b)| {
-                    let mut nodes = [node, a, b];
-                    nodes.sort();
-                    nodes
-                })
-        })
-        .unique()
-        .count();
+    let result =
+        count_triangles(g, |node| node.starts_with("t"));
 
     Ok(result.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+pub fn parse(
+    input: &str,
+) -> IResult<&str, Vec<(&str, &str)>> {
     separated_list1(
         line_ending,
         separated_pair(alpha1, tag("-"), alpha1),
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, line_ending},
@@ -8,52 +7,38 @@ use nom::{
 };
 use petgraph::prelude::UnGraphMap;
 
+use crate::{clique::maximum_cliques, PuzzleError};
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
     let (_input, edges) = parse(input).map_err(|e| {
-        miette::miette!("parsing failed {}", e)
+        PuzzleError::InvalidInput {
+            reason: e.to_string(),
+        }
     })?;
 
     let g = &UnGraphMap::<&str, ()>::from_edges(&edges);
 
-    let output = g
-        .nodes()
-        .flat_map(|node| {
-            g.neighbors(node).combinations(12).filter_map(
-                move |neighbor_subset| {
-                    if neighbor_subset
-                        .iter()
-                        .tuple_combinations()
-                        .all(move |(a, b)| {
-                            g.contains_edge(a, b)
-                        })
-                    {
-                        let mut nodes = vec![node]
-                            .into_iter()
-                            .chain(
-                                neighbor_subset.into_iter(),
-                            )
-                            .collect::<Vec<_>>();
-                        nodes.sort();
-                        Some(nodes)
-                    } else {
-                        None
-                    }
-                },
-            )
-        })
-        .unique()
-        .collect::<Vec<_>>();
-
-    if output.len() == 1 {
-        return Ok(output[0].join(","));
-    } else {
-        dbg!(output);
-        panic!("0 or many answers")
+    // A computer only connected to itself is no party.
+    match &maximum_cliques(g)[..] {
+        [] => Err(PuzzleError::NoLanParty)?,
+        [party, ..] if party.len() < 2 => {
+            Err(PuzzleError::NoLanParty)?
+        }
+        [party] => Ok(party.join(",")),
+        parties => Err(PuzzleError::AmbiguousLanParty {
+            size: parties[0].len(),
+            passwords: parties
+                .iter()
+                .map(|party| party.join(","))
+                .collect(),
+        })?,
     }
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+pub fn parse(
+    input: &str,
+) -> IResult<&str, Vec<(&str, &str)>> {
     separated_list1(
         line_ending,
         separated_pair(alpha1, tag("-"), alpha1),
@@ -101,4 +86,13 @@ td-yn";
         assert_eq!("co,de,ka,ta", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_no_lan_party() {
+        let error = process("ka-ka").unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::NoLanParty),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
 }
@@ -0,0 +1,557 @@
+//! Checks a circuit against the ripple-carry adder it is
+//! meant to be, and repairs it by swapping gate outputs.
+//!
+//! Bit 0 is a half adder and every other bit a full adder:
+//!
+//! ```text
+//! partial   = x XOR y
+//! generate  = x AND y
+//! z         = partial XOR carry_in
+//! propagate = partial AND carry_in
+//! carry_out = generate OR propagate
+//! ```
+//!
+//! and the last carry drives the highest `z` wire. Every
+//! cell is looked up by the wires it reads, from the lowest
+//! bit up, so the first cell that is missing or drives the
+//! wrong wire points at a swapped output.
+
+use std::{collections::HashMap, fmt};
+
+use netlist::{Gate, Netlist, Signal};
+
+use crate::PuzzleError;
+
+/// Random additions [`confirm`] tries by default.
+pub const DEFAULT_TRIALS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    And,
+    Or,
+    Xor,
+}
+
+/// The two-input gates of a circuit, by what they read.
+struct Cells {
+    by_inputs: HashMap<(Kind, String, String), String>,
+}
+
+impl Cells {
+    fn new(circuit: &Netlist) -> Self {
+        let by_inputs = circuit
+            .gates()
+            .filter_map(|(output, gate)| {
+                let (kind, a, b) = match gate {
+                    Gate::And(a, b) => (Kind::And, a, b),
+                    Gate::Or(a, b) => (Kind::Or, a, b),
+                    Gate::Xor(a, b) => (Kind::Xor, a, b),
+                    _ => return None,
+                };
+                let (Signal::Wire(a), Signal::Wire(b)) =
+                    (a, b)
+                else {
+                    return None;
+                };
+                let (a, b) = sorted(a, b);
+                Some((
+                    (kind, a.to_string(), b.to_string()),
+                    output.to_string(),
+                ))
+            })
+            .collect();
+        Self { by_inputs }
+    }
+
+    /// Output of the `kind` gate reading `a` and `b`.
+    fn find(
+        &self,
+        kind: Kind,
+        a: &str,
+        b: &str,
+    ) -> Option<&str> {
+        let (a, b) = sorted(a, b);
+        self.by_inputs
+            .get(&(kind, a.to_string(), b.to_string()))
+            .map(String::as_str)
+    }
+
+    /// The other input of the `kind` gate reading `wire`.
+    /// An adder reads every wire with at most one gate of
+    /// each kind.
+    fn partner(
+        &self,
+        kind: Kind,
+        wire: &str,
+    ) -> Option<&str> {
+        self.by_inputs.keys().find_map(|(k, a, b)| {
+            if *k != kind {
+                None
+            } else if a == wire {
+                Some(b.as_str())
+            } else if b == wire {
+                Some(a.as_str())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+fn sorted<'a>(
+    a: &'a str,
+    b: &'a str,
+) -> (&'a str, &'a str) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// The name of an operand or output wire, `x07`.
+pub fn wire(prefix: char, bit: usize) -> String {
+    format!("{prefix}{bit:02}")
+}
+
+/// Bits of each operand, counted from the `x` wires.
+pub fn input_bits(circuit: &Netlist) -> usize {
+    circuit.wires().filter(|w| w.starts_with('x')).count()
+}
+
+/// Two outputs that had been swapped, with why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub bit: usize,
+    pub wires: [String; 2],
+    pub reason: String,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "bit {:>2}: swapped {} and {}, {}",
+            self.bit,
+            self.wires[0],
+            self.wires[1],
+            self.reason
+        )
+    }
+}
+
+/// Everything [`repair`] swapped back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub bits: usize,
+    pub repairs: Vec<Repair>,
+}
+
+impl Report {
+    /// Every swapped wire, sorted by name.
+    pub fn swapped_wires(&self) -> Vec<&str> {
+        let mut wires = self
+            .repairs
+            .iter()
+            .flat_map(|repair| &repair.wires)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        wires.sort();
+        wires
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if self.repairs.is_empty() {
+            return writeln!(
+                f,
+                "a correct {}-bit adder",
+                self.bits
+            );
+        }
+        for repair in &self.repairs {
+            writeln!(f, "{repair}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Swaps outputs of `circuit` back until every bit is a
+/// correctly wired adder cell.
+pub fn repair(
+    circuit: &mut Netlist,
+) -> Result<Report, PuzzleError> {
+    let bits = input_bits(circuit);
+    let mut repairs: Vec<Repair> = vec![];
+
+    while let Some(repair) = check(circuit, bits)? {
+        // Swapping a pair back again would go in circles.
+        if repairs
+            .iter()
+            .any(|done| done.wires == repair.wires)
+        {
+            return Err(PuzzleError::NotAnAdder {
+                bit: repair.bit,
+                reason: format!(
+                    "{} and {} would be swapped twice",
+                    repair.wires[0], repair.wires[1]
+                ),
+            });
+        }
+        circuit.swap_drivers(
+            &repair.wires[0],
+            &repair.wires[1],
+        )?;
+        repairs.push(repair);
+    }
+    Ok(Report { bits, repairs })
+}
+
+/// The first swap the adder needs, from the lowest bit up,
+/// or `None` once it is wired correctly.
+fn check(
+    circuit: &Netlist,
+    bits: usize,
+) -> Result<Option<Repair>, PuzzleError> {
+    let cells = Cells::new(circuit);
+    let swap =
+        |bit: usize, a: &str, b: &str, reason: String| {
+            let (a, b) = sorted(a, b);
+            Ok(Some(Repair {
+                bit,
+                wires: [a.to_string(), b.to_string()],
+                reason,
+            }))
+        };
+
+    let mut carry: Option<String> = None;
+    for bit in 0..bits {
+        let (x, y, z) = (
+            wire('x', bit),
+            wire('y', bit),
+            wire('z', bit),
+        );
+        let missing =
+            |cell: String| PuzzleError::NotAnAdder {
+                bit,
+                reason: format!("there is no {cell}"),
+            };
+
+        let partial =
+            cells.find(Kind::Xor, &x, &y).ok_or_else(
+                || missing(format!("`{x} XOR {y}`")),
+            )?;
+        let generate =
+            cells.find(Kind::And, &x, &y).ok_or_else(
+                || missing(format!("`{x} AND {y}`")),
+            )?;
+
+        let Some(carry_in) = carry else {
+            if partial != z {
+                return swap(
+                    bit,
+                    partial,
+                    &z,
+                    format!("`{x} XOR {y}` is the sum and has to drive {z}, not {partial}"),
+                );
+            }
+            carry = Some(generate.to_string());
+            continue;
+        };
+
+        let Some(sum) =
+            cells.find(Kind::Xor, partial, &carry_in)
+        else {
+            if let Some(other) =
+                cells.partner(Kind::Xor, &carry_in)
+            {
+                return swap(
+                    bit,
+                    partial,
+                    other,
+                    format!("the sum reads the carry {carry_in} and {other}, but `{x} XOR {y}` drives {partial}"),
+                );
+            }
+            if let Some(other) =
+                cells.partner(Kind::Xor, partial)
+            {
+                return swap(
+                    bit,
+                    &carry_in,
+                    other,
+                    format!("the sum reads `{x} XOR {y}` and {other}, but the carry of bit {} drives {carry_in}", bit - 1),
+                );
+            }
+            return Err(missing(format!(
+                "sum reading {partial} or {carry_in}"
+            )));
+        };
+        if sum != z {
+            return swap(
+                bit,
+                sum,
+                &z,
+                format!("`{partial} XOR {carry_in}` is the sum and has to drive {z}, not {sum}"),
+            );
+        }
+
+        let propagate = cells
+            .find(Kind::And, partial, &carry_in)
+            .ok_or_else(|| {
+                missing(format!(
+                    "`{partial} AND {carry_in}`"
+                ))
+            })?;
+        let Some(carry_out) =
+            cells.find(Kind::Or, generate, propagate)
+        else {
+            if let Some(other) =
+                cells.partner(Kind::Or, generate)
+            {
+                return swap(
+                    bit,
+                    propagate,
+                    other,
+                    format!("the carry reads `{x} AND {y}` and {other}, but `{partial} AND {carry_in}` drives {propagate}"),
+                );
+            }
+            if let Some(other) =
+                cells.partner(Kind::Or, propagate)
+            {
+                return swap(
+                    bit,
+                    generate,
+                    other,
+                    format!("the carry reads `{partial} AND {carry_in}` and {other}, but `{x} AND {y}` drives {generate}"),
+                );
+            }
+            return Err(missing(format!(
+                "carry reading {generate} or {propagate}"
+            )));
+        };
+        carry = Some(carry_out.to_string());
+    }
+
+    let z = wire('z', bits);
+    match carry {
+        Some(carry) if carry != z => swap(
+            bits,
+            &carry,
+            &z,
+            format!("the last carry has to drive {z}, not {carry}"),
+        ),
+        _ => Ok(None),
+    }
+}
+
+/// Adds `trials` pseudo-random pairs of numbers on
+/// `circuit`, after the ones that carry through every bit,
+/// and errors on the first wrong sum. Operands are cut to
+/// 63 bits so that sums fit a `u64`.
+pub fn confirm(
+    circuit: &Netlist,
+    trials: usize,
+) -> Result<(), PuzzleError> {
+    let bits = input_bits(circuit).min(63);
+    let mask = (1 << bits) - 1;
+    let mut circuit = circuit.clone();
+    let mut random = SplitMix64(0x2024_1224);
+
+    let additions = [(0, 0), (mask, 1), (mask, mask)]
+        .into_iter()
+        .chain(
+            std::iter::repeat_with(|| {
+                (
+                    random.next_u64() & mask,
+                    random.next_u64() & mask,
+                )
+            })
+            .take(trials),
+        );
+    for (x, y) in additions {
+        let sum = add(&mut circuit, bits, x, y)?;
+        if sum != x + y {
+            return Err(PuzzleError::WrongSum {
+                x,
+                y,
+                sum,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Sets the `x` and `y` wires to the bits of the operands
+/// and reads the `z` wires back.
+fn add(
+    circuit: &mut Netlist,
+    bits: usize,
+    x: u64,
+    y: u64,
+) -> Result<u64, PuzzleError> {
+    for bit in 0..bits {
+        circuit.override_wire(wire('x', bit), x >> bit & 1);
+        circuit.override_wire(wire('y', bit), y >> bit & 1);
+    }
+    let values = circuit.evaluate()?;
+    Ok((0..=bits)
+        .filter_map(|bit| {
+            values.get(&wire('z', bit)).map(|v| v << bit)
+        })
+        .sum())
+}
+
+/// SplitMix64, enough to pick reproducible additions
+/// without a random number crate.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30))
+            .wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27))
+            .wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    /// A correct `bits`-bit adder, naming the cells of bit 5
+    /// `p05`, `g05`, `a05` and `c05` like in the module docs.
+    fn adder(bits: usize) -> Netlist {
+        let mut circuit = Netlist::new(1).unwrap();
+        let [and, or, xor] =
+            [Gate::And, Gate::Or, Gate::Xor].map(|gate| {
+                move |a: &str, b: &str| {
+                    gate(Signal::wire(a), Signal::wire(b))
+                }
+            });
+
+        let mut carry = String::new();
+        for bit in 0..bits {
+            let [x, y, z, p, g, a, c] =
+                ['x', 'y', 'z', 'p', 'g', 'a', 'c']
+                    .map(|prefix| wire(prefix, bit));
+            let c = if bit == bits - 1 {
+                wire('z', bits)
+            } else {
+                c
+            };
+            circuit.set(&x, 0).unwrap();
+            circuit.set(&y, 0).unwrap();
+            if bit == 0 {
+                circuit.connect(&z, xor(&x, &y)).unwrap();
+                circuit.connect(&c, and(&x, &y)).unwrap();
+            } else {
+                for (output, gate) in [
+                    (&p, xor(&x, &y)),
+                    (&g, and(&x, &y)),
+                    (&z, xor(&p, &carry)),
+                    (&a, and(&p, &carry)),
+                    (&c, or(&g, &a)),
+                ] {
+                    circuit.connect(output, gate).unwrap();
+                }
+            }
+            carry = c;
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_correct_adder() -> Result<(), PuzzleError> {
+        let mut circuit = adder(8);
+        confirm(&circuit, DEFAULT_TRIALS)?;
+
+        let report = repair(&mut circuit)?;
+        assert_eq!(8, report.bits);
+        assert!(report.repairs.is_empty());
+        assert_eq!(
+            "a correct 8-bit adder\n",
+            report.to_string()
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(["c00", "z00"])]
+    #[case(["g05", "p05"])]
+    #[case(["c02", "z03"])]
+    #[case(["g04", "z04"])]
+    #[case(["a06", "z06"])]
+    #[case(["a06", "c06"])]
+    #[case(["z07", "z08"])]
+    fn test_repair(
+        #[case] swapped: [&str; 2],
+    ) -> Result<(), PuzzleError> {
+        let mut circuit = adder(8);
+        circuit.swap_drivers(swapped[0], swapped[1])?;
+        // Some swaps feed a carry back into itself, which
+        // fails as a cycle rather than a wrong sum.
+        assert!(confirm(&circuit, DEFAULT_TRIALS).is_err());
+
+        let report = repair(&mut circuit)?;
+        assert_eq!(
+            swapped.to_vec(),
+            report.swapped_wires()
+        );
+        confirm(&circuit, DEFAULT_TRIALS)
+    }
+
+    #[test]
+    fn test_explanations() -> Result<(), PuzzleError> {
+        let mut circuit = adder(8);
+        circuit.swap_drivers("c02", "z03")?;
+        circuit.swap_drivers("g05", "p05")?;
+
+        let report = repair(&mut circuit)?;
+        assert_eq!(
+            "bit  3: swapped c02 and z03, the sum reads `x03 XOR y03` and c02, but the carry of bit 2 drives z03
+bit  5: swapped g05 and p05, the sum reads the carry c04 and p05, but `x05 XOR y05` drives g05
+",
+            report.to_string()
+        );
+        assert_eq!(
+            vec!["c02", "g05", "p05", "z03"],
+            report.swapped_wires()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_an_adder() -> Result<(), PuzzleError> {
+        let mut circuit = Netlist::new(1)?;
+        circuit.set("x00", 1)?;
+        circuit.set("y00", 1)?;
+        circuit.connect(
+            "z00",
+            Gate::Or(
+                Signal::wire("x00"),
+                Signal::wire("y00"),
+            ),
+        )?;
+        assert_eq!(
+            Err(PuzzleError::NotAnAdder {
+                bit: 0,
+                reason: "there is no `x00 XOR y00`"
+                    .to_string()
+            }),
+            repair(&mut circuit)
+        );
+        Ok(())
+    }
+}
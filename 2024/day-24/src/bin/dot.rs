@@ -0,0 +1,55 @@
+use aoc_input::Args;
+use day_24::{adder::repair, circuit::load};
+use netlist::Netlist;
+
+/// Writes the input's circuit as a GraphViz graph next to
+/// the input, with the outputs the adder verifier swaps
+/// filled in. `--highlight z05,z06` fills in other wires
+/// and `--gates XOR,OR` outlines the gates of those types.
+fn main() -> miette::Result<()> {
+    let args = Args::with_options(
+        std::env::args().skip(1),
+        env!("CARGO_MANIFEST_DIR"),
+        &["--highlight", "--gates"],
+    )?;
+    let source = args.source;
+    let circuit = load(&source.read()?)?;
+
+    let suspects = match args.highlight {
+        Some(wires) => wires,
+        None => suspects(&circuit),
+    };
+
+    let path = source.output_path("dot");
+    let dot = circuit
+        .dot()
+        .highlight_wires(suspects)
+        .highlight_gates(args.gates);
+    std::fs::write(&path, dot.to_string()).map_err(
+        |e| {
+            miette::miette!(
+                "writing {} failed {}",
+                path.display(),
+                e
+            )
+        },
+    )?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+/// The outputs the adder verifier swaps, none when it
+/// cannot repair the circuit.
+fn suspects(circuit: &Netlist) -> Vec<String> {
+    match repair(&mut circuit.clone()) {
+        Ok(report) => report
+            .swapped_wires()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        Err(error) => {
+            eprintln!("{:?}", miette::Report::new(error));
+            vec![]
+        }
+    }
+}
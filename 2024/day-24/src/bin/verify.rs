@@ -0,0 +1,19 @@
+use day_24::{
+    adder::{confirm, repair, DEFAULT_TRIALS},
+    circuit::load,
+};
+
+/// Explains which outputs of the input's adder are swapped,
+/// and checks the repaired circuit on random additions.
+fn main() -> miette::Result<()> {
+    let file = aoc_input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let mut circuit = load(&file)?;
+
+    let report = repair(&mut circuit)?;
+    print!("{report}");
+    confirm(&circuit, DEFAULT_TRIALS)?;
+    println!(
+        "confirmed on {DEFAULT_TRIALS} random additions"
+    );
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use netlist::{Netlist, Signal};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        alphanumeric1, line_ending, multispace1, space1,
+    },
+    combinator::value,
+    multi::separated_list1,
+    sequence::{
+        preceded, separated_pair, terminated, tuple,
+    },
+    IResult,
+};
+
+use crate::PuzzleError;
+
+/// The circuit of the input, with one bit per wire.
+pub fn load(input: &str) -> Result<Netlist, PuzzleError> {
+    let input = input.trim();
+    let (_input, (map, gates)) =
+        parse(input).map_err(|e| {
+            PuzzleError::InvalidInput {
+                reason: e.to_string(),
+            }
+        })?;
+
+    let mut circuit = Netlist::new(1)?;
+    for (wire, value) in map {
+        circuit.set(wire, value.into())?;
+    }
+    for gate in gates {
+        let [a, b] = [gate.inputs[0], gate.inputs[1]]
+            .map(Signal::wire);
+        let cell = match gate.operation {
+            Operation::And => netlist::Gate::And(a, b),
+            Operation::Or => netlist::Gate::Or(a, b),
+            Operation::Xor => netlist::Gate::Xor(a, b),
+        };
+        circuit.connect(gate.output, cell)?;
+    }
+    Ok(circuit)
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gate<'a> {
+    inputs: Vec<&'a str>,
+    output: &'a str,
+    operation: Operation,
+}
+
+fn gate(input: &str) -> IResult<&str, Gate<'_>> {
+    let (input, elements) = tuple((
+        terminated(alphanumeric1, space1),
+        alt((
+            value(Operation::And, tag("AND")),
+            value(Operation::Or, tag("OR")),
+            value(Operation::Xor, tag("XOR")),
+        )),
+        preceded(space1, alphanumeric1),
+        preceded(tag(" -> "), alphanumeric1),
+    ))(input)?;
+
+    Ok((
+        input,
+        Gate {
+            inputs: vec![elements.0, elements.2],
+            output: elements.3,
+            operation: elements.1,
+        },
+    ))
+}
+
+pub fn parse(
+    input: &str,
+) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate<'_>>)> {
+    let (input, map) = separated_list1(
+        line_ending,
+        separated_pair(
+            alphanumeric1,
+            tag(": "),
+            alt((
+                value(false, tag("0")),
+                value(true, tag("1")),
+            )),
+        ),
+    )(input)?;
+
+    let (input, gates) = preceded(
+        multispace1,
+        separated_list1(line_ending, gate),
+    )(input)?;
+
+    let map = map.into_iter().collect();
+
+    Ok((input, (map, gates)))
+}
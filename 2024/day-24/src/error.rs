@@ -0,0 +1,37 @@
+use miette::Diagnostic;
+use netlist::NetlistError;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("initial wire values like `x00: 1`, a blank line, then gates like `x00 AND y00 -> z00`")
+    )]
+    InvalidInput { reason: String },
+    #[error(
+        "the {bits} `z` wires do not make a 64-bit number"
+    )]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help(
+            "the output needs between 1 and 64 `z` wires"
+        )
+    )]
+    OutputWidth { bits: usize },
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Netlist(#[from] NetlistError),
+    #[error("bit {bit} is not part of a ripple-carry adder: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help("only outputs swapped in pairs can be repaired, every adder cell has to exist")
+    )]
+    NotAnAdder { bit: usize, reason: String },
+    #[error(
+        "the repaired circuit adds {x} and {y} up to {sum}"
+    )]
+    #[diagnostic(code(aoc::puzzle::unsolvable))]
+    WrongSum { x: u64, y: u64, sum: u64 },
+}
@@ -1,3 +1,7 @@
-#![feature(extract_if)]
+pub mod adder;
+pub mod circuit;
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
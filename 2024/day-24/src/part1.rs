@@ -1,132 +1,35 @@
-use std::collections::HashMap;
-
-use itertools::Itertools;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{
-        self, alphanumeric1, line_ending, multispace1,
-        space1,
-    },
-    combinator::value,
-    multi::separated_list1,
-    sequence::{
-        preceded, separated_pair, terminated, tuple,
-    },
-    IResult, Parser,
-};
+use crate::{circuit::load, PuzzleError};
 
 pub fn process(input: &str) -> miette::Result<String> {
-    let input = input.trim();
-    let (_input, (map, gates)) =
-        parse(input).map_err(|e| {
-            miette::miette!("parsing failed {}", e)
-        })?;
-
-    let mut current_map = map.clone();
-    let mut gates_to_process = gates.clone();
-    let mut processed_gates: Vec<Gate> = vec![];
-
-    while !gates_to_process.is_empty() {
-        let it = gates_to_process
-            .extract_if(.., |Gate { inputs, .. }| {
-                inputs.iter().all(|input_key| {
-                    current_map.contains_key(input_key)
-                })
-            })
-            .collect::<Vec<_>>();
-
-        for gate in it {
-            let a =
-                current_map.get(gate.inputs[0]).unwrap();
-            let b =
-                current_map.get(gate.inputs[1]).unwrap();
-            let value = match gate.opertaion {
-                Operation::AND => a & b,
-                Operation::OR => a | b,
-                Operation::XOR => a ^ b,
-            };
-
-            current_map.entry(gate.output).or_insert(value);
-            processed_gates.push(gate);
+    let circuit = load(input)?;
+
+    // Each `z` wire holds the bit its number names.
+    let outputs = circuit
+        .evaluate()?
+        .into_iter()
+        .filter_map(|(wire, value)| {
+            let bit =
+                wire.strip_prefix('z')?.parse().ok()?;
+            Some((bit, value))
+        })
+        .collect::<Vec<(u32, u64)>>();
+    if outputs.is_empty()
+        || outputs.iter().any(|&(bit, _)| bit >= u64::BITS)
+    {
+        return Err(PuzzleError::OutputWidth {
+            bits: outputs.len(),
         }
+        .into());
     }
 
-    let bitstring = current_map
+    let result: u64 = outputs
         .iter()
-        .filter(|(key, _)| key.starts_with("z"))
-        .sorted_by(|a, b| b.0.cmp(a.0))
-        .map(|(_, value)| (*value as u8).to_string())
-        .collect::<String>();
-
-    let result =
-        u64::from_str_radix(&bitstring, 2).unwrap();
+        .map(|&(bit, value)| value << bit)
+        .sum();
 
     Ok(result.to_string())
 }
 
-#[derive(Debug, Clone)]
-enum Operation {
-    AND,
-    OR,
-    XOR,
-}
-
-#[derive(Debug, Clone)]
-struct Gate<'a> {
-    inputs: Vec<&'a str>,
-    output: &'a str,
-    opertaion: Operation,
-}
-
-fn gate(input: &str) -> IResult<&str, Gate> {
-    let (input, elements) = tuple((
-        terminated(alphanumeric1, space1),
-        alt((
-            value(Operation::AND, tag("AND")),
-            value(Operation::OR, tag("OR")),
-            value(Operation::XOR, tag("XOR")),
-        )),
-        preceded(space1, alphanumeric1),
-        preceded(tag(" -> "), alphanumeric1),
-    ))(input)?;
-
-    Ok((
-        input,
-        Gate {
-            inputs: vec![elements.0, elements.2],
-            output: elements.3,
-            opertaion: elements.1,
-        },
-    ))
-}
-
-fn parse(
-    input: &str,
-) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate>)> {
-    let (input, map) = separated_list1(
-        line_ending,
-        separated_pair(
-            alphanumeric1,
-            tag(": "),
-            complete::u8.map(|v| match v {
-                0 => false,
-                1 => true,
-                _ => unreachable!(""),
-            }),
-        ),
-    )(input)?;
-
-    let (input, gates) = preceded(
-        multispace1,
-        separated_list1(line_ending, gate),
-    )(input)?;
-
-    let map = map.into_iter().collect();
-
-    Ok((input, (map, gates)))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +101,31 @@ tnw OR pbm -> gnj";
         assert_eq!("2024", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_wire_value() {
+        let error = process("x00: 2\n\nx00 OR x00 -> z00")
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PuzzleError>(),
+            Some(PuzzleError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_output_width() {
+        let error = process("x00: 1\n\nx00 OR x00 -> z64")
+            .unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::OutputWidth { bits: 1 }),
+            error.downcast_ref::<PuzzleError>()
+        );
+
+        let error = process("x00: 1\n\nx00 OR x00 -> a00")
+            .unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::OutputWidth { bits: 0 }),
+            error.downcast_ref::<PuzzleError>()
+        );
+    }
 }
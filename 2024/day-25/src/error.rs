@@ -0,0 +1,18 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(aoc::puzzle::invalid_input),
+        help("locks and keys are 7 rows of `#` and `.`, separated by blank lines")
+    )]
+    InvalidInput { reason: String },
+    #[error("the last day has no second puzzle")]
+    #[diagnostic(
+        code(aoc::puzzle::unsolvable),
+        help("its star comes with the other 49")
+    )]
+    NoPartTwo,
+}
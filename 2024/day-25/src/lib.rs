@@ -1,3 +1,6 @@
 #![feature(extract_if)]
+pub mod error;
 pub mod part1;
 pub mod part2;
+
+pub use error::PuzzleError;
@@ -9,10 +9,14 @@ use nom::{
     IResult,
 };
 
+use crate::PuzzleError;
+
 pub fn process(input: &str) -> miette::Result<String> {
     let input = input.trim();
     let (_input, mut all) = parse(input).map_err(|e| {
-        miette::miette!("parsing failed {}", e)
+        PuzzleError::InvalidInput {
+            reason: e.to_string(),
+        }
     })?;
 
     let locks = all
@@ -66,7 +70,7 @@ fn accumulate_pins(
 }
 
 #[derive(Debug)]
-struct Device {
+pub struct Device {
     r#type: DeviceType,
     pins: [i32; 5],
 }
@@ -108,7 +112,7 @@ fn key(input: &str) -> IResult<&str, Device> {
     ))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Device>> {
+pub fn parse(input: &str) -> IResult<&str, Vec<Device>> {
     separated_list1(line_ending, alt((key, lock)))(input)
 }
 
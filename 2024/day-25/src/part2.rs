@@ -1,5 +1,7 @@
+use crate::PuzzleError;
+
 pub fn process(_input: &str) -> miette::Result<String> {
-    todo!("day 00 - part 2");
+    Err(PuzzleError::NoPartTwo)?
 }
 
 #[cfg(test)]
@@ -7,10 +9,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("TODO: Build Test");
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
+    fn test_process() {
+        let error = process("").unwrap_err();
+        assert_eq!(
+            Some(&PuzzleError::NoPartTwo),
+            error.downcast_ref::<PuzzleError>()
+        );
     }
 }
@@ -0,0 +1,137 @@
+//! Pulling the worked examples out of a cached puzzle page and
+//! writing them as fixture files for a day's tests.
+
+use std::path::{Path, PathBuf};
+
+use crate::{html, ClientError};
+
+/// Directory inside a day crate that holds the fixtures.
+pub const FIXTURES_DIR: &str = "fixtures";
+
+/// The examples of one part of a puzzle description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    /// Contents of every `<pre><code>` block, in order.
+    pub examples: Vec<String>,
+    /// The last emphasised code in the part. The answer to the
+    /// example is written as `<code><em>42</em></code>`, and it
+    /// is the last one before the question is asked in all but
+    /// a handful of days, so check it before relying on it.
+    pub answer: Option<String>,
+}
+
+/// One [`PuzzlePart`] per `<article>` of the page, so part two
+/// is only there once part one has been solved.
+pub fn extract_parts(html: &str) -> Vec<PuzzlePart> {
+    html::articles(html)
+        .map(|article| PuzzlePart {
+            examples: html::elements(article, "<pre><code>", "</code></pre>")
+                .map(html::text)
+                .collect(),
+            answer: last_emphasised_code(article),
+        })
+        .collect()
+}
+
+/// `<code><em>x</em></code>` and `<em><code>x</code></em>` both
+/// show up in descriptions.
+fn last_emphasised_code(article: &str) -> Option<String> {
+    ["<code><em>", "<em><code>"]
+        .into_iter()
+        .filter_map(|open| {
+            let start = article.rfind(open)? + open.len();
+            let end = article[start..].find("</")? + start;
+            Some((start, html::text(&article[start..end])))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| answer)
+}
+
+/// Writes `example-N.txt` for every example, numbered across
+/// both parts, and `partN-answer.txt` for every part with an
+/// answer into `dir`. Existing fixtures are overwritten, they
+/// are generated from the page.
+pub fn write_fixtures(parts: &[PuzzlePart], dir: &Path) -> Result<Vec<PathBuf>, ClientError> {
+    let examples = parts
+        .iter()
+        .flat_map(|part| &part.examples)
+        .enumerate()
+        .map(|(i, example)| (format!("example-{}.txt", i + 1), example));
+    let answers = parts.iter().enumerate().filter_map(|(i, part)| {
+        let answer = part.answer.as_ref()?;
+        Some((format!("part{}-answer.txt", i + 1), answer))
+    });
+
+    std::fs::create_dir_all(dir).map_err(|source| ClientError::Write {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    examples
+        .chain(answers)
+        .map(|(name, contents)| {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).map_err(|source| ClientError::Write {
+                path: path.clone(),
+                source,
+            })?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_parts() {
+        let parts = extract_parts(include_str!("../fixtures/puzzle.html"));
+
+        assert_eq!(
+            vec![
+                PuzzlePart {
+                    examples: vec!["3\n7\n5\n<9>\n12\n".to_string()],
+                    answer: Some("2".to_string()),
+                },
+                PuzzlePart {
+                    examples: vec!["1\n2\n3\n".to_string()],
+                    answer: Some("3".to_string()),
+                },
+            ],
+            parts
+        );
+    }
+
+    #[test]
+    fn test_write_fixtures() -> Result<(), ClientError> {
+        let dir = std::env::temp_dir().join(format!("aoc-client-fixtures-{}", std::process::id()));
+        let parts = extract_parts(include_str!("../fixtures/puzzle.html"));
+
+        let written = write_fixtures(&parts, &dir)?;
+
+        let names: Vec<_> = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                "example-1.txt",
+                "example-2.txt",
+                "part1-answer.txt",
+                "part2-answer.txt"
+            ],
+            names
+        );
+        assert_eq!(
+            "1\n2\n3\n",
+            std::fs::read_to_string(dir.join("example-2.txt")).unwrap()
+        );
+        assert_eq!(
+            "2",
+            std::fs::read_to_string(dir.join("part1-answer.txt")).unwrap()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
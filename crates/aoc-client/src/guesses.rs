@@ -0,0 +1,256 @@
+//! Local record of every answer the site has judged, kept in
+//! `guesses.toml` next to a day's input.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// File name of the recorded guesses inside a day crate.
+pub const GUESSES_FILE: &str = "guesses.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "that's the right answer"),
+            Verdict::TooHigh => write!(f, "wrong, the answer is too high"),
+            Verdict::TooLow => write!(f, "wrong, the answer is too low"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+        }
+    }
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum GuessError {
+    #[error("failed to read guesses from {}", path.display())]
+    #[diagnostic(code(aoc::guesses::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("guesses in {} are not valid", path.display())]
+    #[diagnostic(code(aoc::guesses::parse))]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("failed to write guesses to {}", path.display())]
+    #[diagnostic(code(aoc::guesses::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("part {part} is already solved with `{answer}`")]
+    #[diagnostic(code(aoc::guesses::solved))]
+    Solved { part: u8, answer: String },
+    #[error("`{answer}` was already submitted for part {part}: {verdict}")]
+    #[diagnostic(code(aoc::guesses::known))]
+    AlreadyGuessed {
+        part: u8,
+        answer: String,
+        verdict: Verdict,
+    },
+    #[error("`{answer}` is out of bounds for part {part}, `{bound}` was already {verdict}")]
+    #[diagnostic(
+        code(aoc::guesses::bounds),
+        help("the answer has to be between the highest too low and the lowest too high guess")
+    )]
+    OutOfBounds {
+        part: u8,
+        answer: String,
+        bound: String,
+        verdict: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guesses {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    part1: Vec<Guess>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    part2: Vec<Guess>,
+}
+
+impl Guesses {
+    /// Loads the guesses at `path`, nothing has been guessed
+    /// when the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GuessError> {
+        let path = path.as_ref();
+        let input = match std::fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(source) if source.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(GuessError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        toml::from_str(&input).map_err(|source| GuessError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GuessError> {
+        let path = path.as_ref();
+        let output = toml::to_string(self).expect("guesses to serialize");
+        std::fs::write(path, output).map_err(|source| GuessError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn part(&self, part: u8) -> &[Guess] {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, verdict: Verdict) {
+        let guesses = match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        };
+        guesses.push(Guess {
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    /// Errors when submitting `answer` cannot be right: the part
+    /// is solved, the same answer was judged before, or a
+    /// numeric answer is outside the bounds set by earlier too
+    /// high and too low verdicts.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), GuessError> {
+        let guesses = self.part(part);
+
+        if let Some(solved) = guesses
+            .iter()
+            .find(|guess| guess.verdict == Verdict::Correct)
+        {
+            return Err(GuessError::Solved {
+                part,
+                answer: solved.answer.clone(),
+            });
+        }
+        if let Some(guess) = guesses.iter().find(|guess| guess.answer == answer) {
+            return Err(GuessError::AlreadyGuessed {
+                part,
+                answer: answer.to_string(),
+                verdict: guess.verdict,
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        for guess in guesses {
+            let Ok(bound) = guess.answer.parse::<i128>() else {
+                continue;
+            };
+            let verdict = match guess.verdict {
+                Verdict::TooHigh if value >= bound => "too high",
+                Verdict::TooLow if value <= bound => "too low",
+                _ => continue,
+            };
+            return Err(GuessError::OutOfBounds {
+                part,
+                answer: answer.to_string(),
+                bound: guess.answer.clone(),
+                verdict,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut guesses = Guesses::default();
+        assert!(guesses.check(1, "500").is_ok());
+
+        guesses.record(1, "1000", Verdict::TooHigh);
+        guesses.record(1, "100", Verdict::TooLow);
+        guesses.record(1, "400", Verdict::Wrong);
+        guesses.record(2, "4,6,3", Verdict::Wrong);
+
+        assert!(guesses.check(1, "500").is_ok());
+        assert!(matches!(
+            guesses.check(1, "400"),
+            Err(GuessError::AlreadyGuessed {
+                verdict: Verdict::Wrong,
+                ..
+            })
+        ));
+        assert!(matches!(
+            guesses.check(1, "1200"),
+            Err(GuessError::OutOfBounds { bound, .. }) if bound == "1000"
+        ));
+        assert!(matches!(
+            guesses.check(1, "100"),
+            Err(GuessError::AlreadyGuessed { .. })
+        ));
+        assert!(matches!(
+            guesses.check(1, "-5"),
+            Err(GuessError::OutOfBounds { bound, .. }) if bound == "100"
+        ));
+        assert!(matches!(
+            guesses.check(2, "4,6,3"),
+            Err(GuessError::AlreadyGuessed { .. })
+        ));
+        assert!(guesses.check(2, "4,6,4").is_ok());
+
+        guesses.record(1, "512", Verdict::Correct);
+        assert!(matches!(
+            guesses.check(1, "513"),
+            Err(GuessError::Solved { answer, .. }) if answer == "512"
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load() -> Result<(), GuessError> {
+        let path = std::env::temp_dir().join(format!("aoc-guesses-{}.toml", std::process::id()));
+
+        assert_eq!(Guesses::default(), Guesses::load(&path)?);
+
+        let mut guesses = Guesses::default();
+        guesses.record(2, "17", Verdict::TooLow);
+        guesses.record(2, "42", Verdict::Correct);
+        guesses.save(&path)?;
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("[[part2]]\nanswer = \"17\"\nverdict = \"too-low\""));
+        assert_eq!(guesses, Guesses::load(&path)?);
+
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+}
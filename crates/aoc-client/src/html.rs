@@ -0,0 +1,94 @@
+//! Just enough HTML handling for the pages the site serves,
+//! which are regular enough to not need a full parser.
+
+/// Inner HTML of every `<article>`, in page order.
+pub fn articles(html: &str) -> impl Iterator<Item = &str> {
+    elements(html, "<article", "</article>").map(|article| {
+        let start = article.find('>').map_or(article.len(), |end| end + 1);
+        &article[start..]
+    })
+}
+
+/// Every `open ... close` stretch of `html`, without the
+/// closing tag.
+pub fn elements<'a>(html: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = html;
+    std::iter::from_fn(move || {
+        let start = rest.find(open)?;
+        let end = rest[start..].find(close)? + start;
+        let element = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(element)
+    })
+}
+
+/// Text of an HTML fragment, with tags stripped and entities
+/// decoded.
+pub fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end + 1))
+            .and_then(|(name, len)| Some((entity(name)?, len)));
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => match name.strip_prefix('#')? {
+            hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok(),
+            decimal => decimal.parse().ok(),
+        }
+        .and_then(char::from_u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "a < b && \"c\" 'd' & e",
+            text("<p><code>a &lt; b</code> &amp;&amp; &quot;c&quot; &#39;d&#x27; & e</p>")
+        );
+    }
+}
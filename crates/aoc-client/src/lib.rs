@@ -0,0 +1,450 @@
+//! Blocking client for the Advent of Code website.
+//!
+//! Inputs and puzzle pages are cached on disk:
+//! [`Client::fetch_input_to`] and [`Client::fetch_puzzle_to`]
+//! never request a file that already exists, and
+//! [`extract_parts`] reads the examples out of a cached page.
+//! Answers go through [`Client::submit`], with [`Guesses`]
+//! remembering every verdict so a known-wrong answer is never
+//! sent twice.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+mod examples;
+mod guesses;
+mod html;
+mod submit;
+#[cfg(test)]
+mod test_server;
+
+pub use examples::{extract_parts, write_fixtures, PuzzlePart, FIXTURES_DIR};
+pub use guesses::{GuessError, Guesses, Verdict, GUESSES_FILE};
+pub use submit::{parse_outcome, Outcome};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// File name of the cached puzzle description inside a day
+/// crate.
+pub const PUZZLE_FILE: &str = "puzzle.html";
+
+/// Sent with every request, as asked for by the Advent of Code
+/// automation guidelines. Override it with `AOC_USER_AGENT` to
+/// add contact details.
+pub const USER_AGENT: &str = concat!(
+    "aoc-client/",
+    env!("CARGO_PKG_VERSION"),
+    " (advent-of-code solutions runner)"
+);
+
+/// Environment variable holding the `session` cookie.
+pub const SESSION_VAR: &str = "SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`].
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable overriding [`USER_AGENT`].
+pub const USER_AGENT_VAR: &str = "AOC_USER_AGENT";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ClientError {
+    #[error("no session cookie, `{SESSION_VAR}` is not set")]
+    #[diagnostic(
+        code(aoc::client::session),
+        help("copy the `session` cookie from adventofcode.com into `SESSION=` in your .env")
+    )]
+    MissingSession,
+    #[error("not logged in when requesting {url}")]
+    #[diagnostic(
+        code(aoc::client::logged_out),
+        help("the session cookie is missing or expired, grab a fresh one from the browser")
+    )]
+    NotLoggedIn { url: String },
+    #[error("{year} day {day} is not unlocked yet")]
+    #[diagnostic(code(aoc::client::locked))]
+    Locked { year: u16, day: u8 },
+    #[error("{url} responded with status {status}")]
+    #[diagnostic(code(aoc::client::status))]
+    Status { url: String, status: u16 },
+    #[error("request to {url} failed")]
+    #[diagnostic(code(aoc::client::transport))]
+    Transport {
+        url: String,
+        #[source]
+        source: Box<ureq::Transport>,
+    },
+    #[error("failed to read the response from {url}")]
+    #[diagnostic(code(aoc::client::response))]
+    Response {
+        url: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("unexpected response from {url}: {text}")]
+    #[diagnostic(code(aoc::client::unexpected))]
+    UnexpectedResponse { url: String, text: String },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::client::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Whether [`Client::fetch_input_to`] or
+/// [`Client::fetch_puzzle_to`] had to go to the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+impl fmt::Display for Fetched {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fetched::Cached => write!(f, "cached"),
+            Fetched::Downloaded => write!(f, "downloaded"),
+        }
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self::with_user_agent(session, USER_AGENT)
+    }
+
+    pub fn with_user_agent(session: impl Into<String>, user_agent: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(user_agent).build(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Reads the session, base url and user agent from
+    /// `SESSION`, `AOC_BASE_URL` and `AOC_USER_AGENT`.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = std::env::var(SESSION_VAR)
+            .ok()
+            .filter(|session| !session.trim().is_empty())
+            .ok_or(ClientError::MissingSession)?;
+        let user_agent = std::env::var(USER_AGENT_VAR).unwrap_or_else(|_| USER_AGENT.to_string());
+
+        let client = Self::with_user_agent(session.trim(), &user_agent);
+        Ok(match std::env::var(BASE_URL_VAR) {
+            Ok(base_url) => client.base_url(base_url),
+            Err(_) => client,
+        })
+    }
+
+    /// Points the client at another server, e.g. a local
+    /// stand-in for tests.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn day_url(&self, year: u16, day: u8) -> String {
+        format!("{}/{year}/day/{day}", self.base_url)
+    }
+
+    /// Downloads the puzzle input of a day.
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        match self.send(self.agent.get(&url), None) {
+            Err(ClientError::Status { status: 404, .. }) => Err(ClientError::Locked { year, day }),
+            result => result,
+        }
+    }
+
+    /// Downloads the puzzle description page of a day. Part
+    /// two is only on the page once part one is solved.
+    pub fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = self.day_url(year, day);
+        match self.send(self.agent.get(&url), None) {
+            Err(ClientError::Status { status: 404, .. }) => Err(ClientError::Locked { year, day }),
+            result => result,
+        }
+    }
+
+    /// Sends `request` with the session cookie, as a form post
+    /// when `form` is given, and returns the response body.
+    /// Error statuses and logged out pages become errors.
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, ClientError> {
+        let url = request.url().to_string();
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        let body = match response {
+            Ok(response) => read_body(&url, response)?,
+            Err(ureq::Error::Status(status, response)) => {
+                let body = read_body(&url, response).unwrap_or_default();
+                return Err(if is_logged_out(&body) {
+                    ClientError::NotLoggedIn { url }
+                } else {
+                    ClientError::Status { url, status }
+                });
+            }
+            Err(ureq::Error::Transport(source)) => {
+                return Err(ClientError::Transport {
+                    url,
+                    source: Box::new(source),
+                })
+            }
+        };
+
+        if is_logged_out(&body) {
+            return Err(ClientError::NotLoggedIn { url });
+        }
+        Ok(body)
+    }
+
+    /// Downloads the input of a day into `path` unless the file
+    /// already exists, creating parent directories as needed.
+    pub fn fetch_input_to(
+        &self,
+        year: u16,
+        day: u8,
+        path: impl AsRef<Path>,
+    ) -> Result<Fetched, ClientError> {
+        fetch_to(path.as_ref(), || self.fetch_input(year, day))
+    }
+
+    /// Downloads the puzzle page of a day into `path` unless the
+    /// file already exists, like [`Client::fetch_input_to`].
+    pub fn fetch_puzzle_to(
+        &self,
+        year: u16,
+        day: u8,
+        path: impl AsRef<Path>,
+    ) -> Result<Fetched, ClientError> {
+        fetch_to(path.as_ref(), || self.fetch_puzzle(year, day))
+    }
+
+    /// Downloads the puzzle page of a day into `path` even when
+    /// the file already exists. The old page is only replaced
+    /// once the new one has been fetched and written in full.
+    pub fn refresh_puzzle_to(
+        &self,
+        year: u16,
+        day: u8,
+        path: impl AsRef<Path>,
+    ) -> Result<Fetched, ClientError> {
+        let contents = self.fetch_puzzle(year, day)?;
+        write_atomically(path.as_ref(), &contents)?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+fn fetch_to(
+    path: &Path,
+    fetch: impl FnOnce() -> Result<String, ClientError>,
+) -> Result<Fetched, ClientError> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let contents = fetch()?;
+    write_atomically(path, &contents)?;
+    Ok(Fetched::Downloaded)
+}
+
+/// Writes `contents` to a temporary file next to `path` and
+/// renames it over `path`, so readers never see a partial file.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), ClientError> {
+    let write = |source| ClientError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(write)?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    std::fs::write(&temp, contents)
+        .and_then(|()| std::fs::rename(&temp, path))
+        .map_err(|source| {
+            let _ = std::fs::remove_file(&temp);
+            write(source)
+        })
+}
+
+fn read_body(url: &str, response: ureq::Response) -> Result<String, ClientError> {
+    response
+        .into_string()
+        .map_err(|source| ClientError::Response {
+            url: url.to_string(),
+            source,
+        })
+}
+
+/// The site answers requests without a valid session with a
+/// "Puzzle inputs differ by user. Please log in to get your
+/// puzzle input." or a "To play, please identify yourself"
+/// page instead of the input or verdict.
+fn is_logged_out(body: &str) -> bool {
+    body.contains("Puzzle inputs differ by user")
+        || body.contains("Please log in")
+        || body.contains("To play, please identify yourself")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_server::TestServer;
+
+    const INPUT: &str = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n";
+
+    #[test]
+    fn test_fetch_input() -> Result<(), ClientError> {
+        let server = TestServer::start(vec![(200, INPUT)]);
+        let client = Client::new("abc123").base_url(&server.url);
+
+        assert_eq!(INPUT, client.fetch_input(2024, 17)?);
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/2024/day/17/input", requests[0].path);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+        assert_eq!(Some(USER_AGENT), requests[0].header("User-Agent"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_input_errors() {
+        let server = TestServer::start(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (500, "Internal Server Error"),
+        ]);
+        let client = Client::new("expired").base_url(format!("{}/", server.url));
+
+        assert!(matches!(
+            client.fetch_input(2015, 1),
+            Err(ClientError::NotLoggedIn { url }) if url.ends_with("/2015/day/1/input")
+        ));
+        assert!(matches!(
+            client.fetch_input(2024, 25),
+            Err(ClientError::Locked {
+                year: 2024,
+                day: 25
+            })
+        ));
+        assert!(matches!(
+            client.fetch_input(2024, 1),
+            Err(ClientError::Status { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fetch_input_to_never_refetches() -> Result<(), ClientError> {
+        let server = TestServer::start(vec![(200, INPUT)]);
+        let client = Client::new("abc123").base_url(&server.url);
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let path = dir.join("day-17").join("input.txt");
+
+        assert_eq!(Fetched::Downloaded, client.fetch_input_to(2024, 17, &path)?);
+        assert_eq!(Fetched::Cached, client.fetch_input_to(2024, 17, &path)?);
+        assert_eq!(INPUT, std::fs::read_to_string(&path).unwrap());
+        assert_eq!(1, server.requests().len());
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_puzzle_to() -> Result<(), ClientError> {
+        let page = include_str!("../fixtures/puzzle.html");
+        let server = TestServer::start(vec![(200, page)]);
+        let client = Client::new("abc123").base_url(&server.url);
+        let dir = std::env::temp_dir().join(format!("aoc-client-puzzle-{}", std::process::id()));
+        let path = dir.join(PUZZLE_FILE);
+
+        assert_eq!(Fetched::Downloaded, client.fetch_puzzle_to(2024, 3, &path)?);
+        assert_eq!(Fetched::Cached, client.fetch_puzzle_to(2024, 3, &path)?);
+        assert_eq!(page, std::fs::read_to_string(&path).unwrap());
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("/2024/day/3", requests[0].path);
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_refresh_puzzle_to() -> Result<(), ClientError> {
+        let page = include_str!("../fixtures/puzzle.html");
+        let server = TestServer::start(vec![(500, "Internal Server Error"), (200, page)]);
+        let client = Client::new("abc123").base_url(&server.url);
+        let dir = std::env::temp_dir().join(format!("aoc-client-refresh-{}", std::process::id()));
+        let path = dir.join(PUZZLE_FILE);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "stale").unwrap();
+
+        assert!(matches!(
+            client.refresh_puzzle_to(2024, 3, &path),
+            Err(ClientError::Status { status: 500, .. })
+        ));
+        assert_eq!("stale", std::fs::read_to_string(&path).unwrap());
+
+        assert_eq!(
+            Fetched::Downloaded,
+            client.refresh_puzzle_to(2024, 3, &path)?
+        );
+        assert_eq!(page, std::fs::read_to_string(&path).unwrap());
+        assert_eq!(
+            vec![PUZZLE_FILE],
+            std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_fetch_writes_nothing() {
+        let server = TestServer::start(vec![(
+            200,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        let client = Client::new("").base_url(&server.url);
+        let path = std::env::temp_dir()
+            .join(format!("aoc-client-logged-out-{}", std::process::id()))
+            .join("input.txt");
+
+        assert!(matches!(
+            client.fetch_input_to(2024, 1, &path),
+            Err(ClientError::NotLoggedIn { .. })
+        ));
+        assert!(!path.exists());
+    }
+}
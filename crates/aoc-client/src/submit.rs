@@ -0,0 +1,181 @@
+//! Posting answers and reading the verdict out of the page
+//! the site responds with.
+
+use std::{fmt, time::Duration};
+
+use crate::{guesses::Verdict, html, Client, ClientError};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer was checked, see [`Verdict`].
+    Answered(Verdict),
+    /// Submitted too soon after the last wrong answer, the
+    /// answer was not checked.
+    RateLimited { wait: Duration },
+    /// The part has already been solved on this account.
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answered(verdict) => write!(f, "{verdict}"),
+            Outcome::RateLimited { wait } => {
+                write!(
+                    f,
+                    "rate limited, wait {}s before trying again",
+                    wait.as_secs()
+                )
+            }
+            Outcome::AlreadySolved => write!(f, "this part is already solved"),
+        }
+    }
+}
+
+impl Client {
+    /// Posts `answer` for a part of a day.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Outcome, ClientError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let level = part.to_string();
+        let body = self.send(
+            self.agent.post(&url),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+
+        parse_outcome(&body).ok_or_else(|| ClientError::UnexpectedResponse {
+            url,
+            text: article_text(&body).unwrap_or(body),
+        })
+    }
+}
+
+/// Reads the outcome from the `<article>` of an answer page.
+pub fn parse_outcome(html: &str) -> Option<Outcome> {
+    let text = article_text(html)?;
+
+    if text.contains("That's the right answer") {
+        Some(Outcome::Answered(Verdict::Correct))
+    } else if text.contains("That's not the right answer") {
+        Some(Outcome::Answered(if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }))
+    } else if text.contains("You gave an answer too recently") {
+        Some(Outcome::RateLimited {
+            wait: wait_time(&text).unwrap_or(Duration::from_secs(60)),
+        })
+    } else if text.contains("You don't seem to be solving the right level") {
+        Some(Outcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+/// Text of the first `<article>` with the markup stripped.
+fn article_text(html: &str) -> Option<String> {
+    html::articles(html)
+        .next()
+        .map(|article| html::text(article).trim().to_string())
+}
+
+/// `You have 4m 12s left to wait.` as a duration.
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 60 * 60),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        include_str!("../fixtures/answer_correct.html"),
+        Outcome::Answered(Verdict::Correct)
+    )]
+    #[case(
+        include_str!("../fixtures/answer_too_high.html"),
+        Outcome::Answered(Verdict::TooHigh)
+    )]
+    #[case(
+        include_str!("../fixtures/answer_too_low.html"),
+        Outcome::Answered(Verdict::TooLow)
+    )]
+    #[case(
+        include_str!("../fixtures/answer_wrong.html"),
+        Outcome::Answered(Verdict::Wrong)
+    )]
+    #[case(
+        include_str!("../fixtures/answer_rate_limited.html"),
+        Outcome::RateLimited { wait: Duration::from_secs(4 * 60 + 12) }
+    )]
+    #[case(
+        include_str!("../fixtures/answer_already_solved.html"),
+        Outcome::AlreadySolved
+    )]
+    fn test_parse_outcome(#[case] html: &str, #[case] outcome: Outcome) {
+        assert_eq!(Some(outcome), parse_outcome(html));
+    }
+
+    #[test]
+    fn test_submit() -> Result<(), ClientError> {
+        let server = TestServer::start(vec![
+            (200, include_str!("../fixtures/answer_too_low.html")),
+            (200, include_str!("../fixtures/answer_correct.html")),
+            (
+                200,
+                "<html><main><article><p>Something else</p></article></main></html>",
+            ),
+        ]);
+        let client = Client::new("abc123").base_url(&server.url);
+
+        assert_eq!(
+            Outcome::Answered(Verdict::TooLow),
+            client.submit(2024, 7, 1, "1234")?
+        );
+        assert_eq!(
+            Outcome::Answered(Verdict::Correct),
+            client.submit(2024, 7, 2, "4,6,3,5")?
+        );
+        assert!(matches!(
+            client.submit(2024, 7, 2, "1"),
+            Err(ClientError::UnexpectedResponse { text, .. }) if text == "Something else"
+        ));
+
+        let requests = server.requests();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/2024/day/7/answer", requests[0].path);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+        assert_eq!("level=1&answer=1234", requests[0].body);
+        assert_eq!("level=2&answer=4%2C6%2C3%2C5", requests[1].body);
+        Ok(())
+    }
+}
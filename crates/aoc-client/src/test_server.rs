@@ -0,0 +1,75 @@
+//! Local stand-in for adventofcode.com that answers requests
+//! with canned responses, in order, and records what it was
+//! sent.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tiny_http::{Response, Server};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct TestServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    /// Serves one `(status, body)` response per request, then
+    /// shuts down.
+    pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+        let server = Server::http("127.0.0.1:0").expect("a free local port");
+        let url = format!("http://{}", server.server_addr());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok(mut request) = server.recv() else {
+                    return;
+                };
+                let mut body_sent = String::new();
+                request
+                    .as_reader()
+                    .read_to_string(&mut body_sent)
+                    .expect("a utf-8 request body");
+                recorded.lock().unwrap().push(Request {
+                    method: request.method().to_string(),
+                    path: request.url().to_string(),
+                    headers: request
+                        .headers()
+                        .iter()
+                        .map(|header| (header.field.to_string(), header.value.to_string()))
+                        .collect(),
+                    body: body_sent,
+                });
+                request
+                    .respond(Response::from_string(body).with_status_code(status))
+                    .expect("the response to be sent");
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,413 @@
+//! Runtime loading of puzzle input for the day binaries.
+//!
+//! Input comes from `--input <file>`, from stdin when the
+//! file is `-`, or from `input.txt` in the day's crate
+//! directory when nothing is passed. Binaries that draw the
+//! puzzle also take `--frames <dir>`, and the ones writing a
+//! graph `--highlight <list>` and `--gates <list>`, see
+//! [`Args`].
+
+use std::{
+    fmt, io,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// File name of the puzzle input inside a day crate.
+pub const INPUT_FILE: &str = "input.txt";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum InputError {
+    #[error("puzzle input not found at {}", path.display())]
+    #[diagnostic(
+        code(aoc::input::missing),
+        help("fetch it with `just get-input {day}`, or pass `--input <file>` (`-` reads stdin)")
+    )]
+    Missing { path: PathBuf, day: String },
+    #[error("failed to read puzzle input from {}", path.display())]
+    #[diagnostic(code(aoc::input::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to read puzzle input from stdin")]
+    #[diagnostic(code(aoc::input::stdin))]
+    Stdin(#[source] io::Error),
+    #[error("`--input` expects a file path, or `-` for stdin")]
+    #[diagnostic(code(aoc::input::args))]
+    MissingValue,
+    #[error("`--frames` expects a directory to write images to")]
+    #[diagnostic(code(aoc::input::args))]
+    MissingFramesDir,
+    #[error("`{0}` expects a comma-separated list, like `a,b,c`")]
+    #[diagnostic(code(aoc::input::args))]
+    MissingList(String),
+    #[error("unexpected argument `{0}`")]
+    #[diagnostic(
+        code(aoc::input::args),
+        help("the supported arguments are `--input <file>`, `--frames <dir>` for days that draw the puzzle, and `--highlight <list>` or `--gates <list>` for days that write a graph")
+    )]
+    UnexpectedArgument(String),
+}
+
+/// Where the puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a file path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if path.as_os_str() == "-" {
+            Source::Stdin
+        } else {
+            Source::File(path)
+        }
+    }
+
+    /// The default `input.txt` inside a day crate
+    /// directory.
+    pub fn default_for(day_dir: impl AsRef<Path>) -> Self {
+        Source::File(day_dir.as_ref().join(INPUT_FILE))
+    }
+
+    /// Resolves the source from command line arguments
+    /// (without the program name), falling back to the
+    /// day's `input.txt`.
+    pub fn from_args<I>(args: I, day_dir: impl AsRef<Path>) -> Result<Self, InputError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Ok(parse_args(args, day_dir, &[])?.source)
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Source::File(path) => std::fs::read_to_string(path).map_err(|source| {
+                if source.kind() == io::ErrorKind::NotFound {
+                    InputError::Missing {
+                        path: path.clone(),
+                        day: day_name(path),
+                    }
+                } else {
+                    InputError::Read {
+                        path: path.clone(),
+                        source,
+                    }
+                }
+            }),
+        }
+    }
+
+    /// Where to write a file made from the input: the input
+    /// path with `extension`, `input.dot` next to
+    /// `input.txt`. Input from stdin gets `input.<extension>`
+    /// in the current directory.
+    pub fn output_path(&self, extension: &str) -> PathBuf {
+        match self {
+            Source::File(path) => path.with_extension(extension),
+            Source::Stdin => Path::new(INPUT_FILE).with_extension(extension),
+        }
+    }
+}
+
+/// Arguments of a binary that can draw the puzzle: the input
+/// as for [`Source::from_args`], and with `--frames <dir>`
+/// a directory to write images of its states to.
+///
+/// Binaries writing a graph take `--highlight a,b,c` for the
+/// nodes to fill in instead of the ones they pick, and some
+/// `--gates XOR,OR` for the gate types to outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub source: Source,
+    pub frames: Option<PathBuf>,
+    pub highlight: Option<Vec<String>>,
+    pub gates: Vec<String>,
+}
+
+impl Args {
+    pub fn from_args<I>(args: I, day_dir: impl AsRef<Path>) -> Result<Self, InputError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self::with_options(args, day_dir, &["--frames"])
+    }
+
+    /// [`Args::from_args`] for the arguments of the process.
+    pub fn from_env(day_dir: impl AsRef<Path>) -> Result<Self, InputError> {
+        Self::from_args(std::env::args().skip(1), day_dir)
+    }
+
+    /// Accepts `--input` and the listed options, among
+    /// `--frames`, `--highlight` and `--gates`, rejecting the
+    /// others.
+    pub fn with_options<I>(
+        args: I,
+        day_dir: impl AsRef<Path>,
+        options: &[&str],
+    ) -> Result<Self, InputError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        parse_args(args, day_dir, options)
+    }
+}
+
+/// Only the `options` are accepted besides `--input`, every
+/// other binary keeps rejecting them.
+fn parse_args<I>(args: I, day_dir: impl AsRef<Path>, options: &[&str]) -> Result<Args, InputError>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    let mut source = None;
+    let mut frames = None;
+    let mut highlight: Option<Vec<String>> = None;
+    let mut gates = vec![];
+    let mut args = args.into_iter().map(Into::into);
+
+    while let Some(arg) = args.next() {
+        if arg == "-" {
+            source = Some(Source::Stdin);
+            continue;
+        }
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let name = match name {
+            "-i" if inline.is_none() => "--input",
+            "--input" => "--input",
+            name if options.contains(&name) => name,
+            _ => return Err(InputError::UnexpectedArgument(arg)),
+        };
+        let value = inline.or_else(|| args.next());
+        match name {
+            "--input" => {
+                source = Some(Source::new(value.ok_or(InputError::MissingValue)?));
+            }
+            "--frames" => {
+                frames = Some(value.ok_or(InputError::MissingFramesDir)?.into());
+            }
+            _ => {
+                let value = value.ok_or_else(|| InputError::MissingList(name.to_string()))?;
+                let list = value
+                    .split(',')
+                    .filter(|item| !item.is_empty())
+                    .map(String::from);
+                match name {
+                    "--highlight" => highlight.get_or_insert_with(Vec::new).extend(list),
+                    _ => gates.extend(list),
+                }
+            }
+        }
+    }
+
+    Ok(Args {
+        source: source.unwrap_or_else(|| Source::default_for(day_dir)),
+        frames,
+        highlight,
+        gates,
+    })
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Loads the input for a day binary, resolving the source
+/// from the process arguments. `day_dir` is the day crate's
+/// directory, usually `env!("CARGO_MANIFEST_DIR")`.
+pub fn load(day_dir: impl AsRef<Path>) -> miette::Result<String> {
+    let source = Source::from_args(std::env::args().skip(1), day_dir)?;
+    Ok(source.read()?)
+}
+
+/// Reads the day's `input.txt` without looking at the process
+/// arguments, `None` when it has not been fetched yet. Used by
+/// benchmarks and the regression suite, which skip days without
+/// an input.
+pub fn local(day_dir: impl AsRef<Path>) -> Result<Option<String>, InputError> {
+    match Source::default_for(day_dir).read() {
+        Ok(input) => Ok(Some(input)),
+        Err(InputError::Missing { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// `day-17` for `.../2024/day-17/input.txt`, used in the
+/// help text of a missing input.
+fn day_name(path: &Path) -> String {
+    path.parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "<day>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_args() -> Result<(), InputError> {
+        let dir = Path::new("2024/day-17");
+
+        assert_eq!(
+            Source::File(dir.join("input.txt")),
+            Source::from_args(Vec::<String>::new(), dir)?
+        );
+        assert_eq!(
+            Source::File("other.txt".into()),
+            Source::from_args(["--input", "other.txt"], dir)?
+        );
+        assert_eq!(
+            Source::File("other.txt".into()),
+            Source::from_args(["--input=other.txt"], dir)?
+        );
+        assert_eq!(Source::Stdin, Source::from_args(["-i", "-"], dir)?);
+        assert_eq!(Source::Stdin, Source::from_args(["-"], dir)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_args_errors() {
+        let dir = Path::new("2024/day-17");
+
+        assert!(matches!(
+            Source::from_args(["--input"], dir),
+            Err(InputError::MissingValue)
+        ));
+        assert!(matches!(
+            Source::from_args(["input.txt"], dir),
+            Err(InputError::UnexpectedArgument(arg)) if arg == "input.txt"
+        ));
+    }
+
+    #[test]
+    fn test_frames_args() -> Result<(), InputError> {
+        let dir = Path::new("2024/day-14");
+
+        assert_eq!(
+            Args {
+                source: Source::Stdin,
+                frames: Some("frames".into()),
+                highlight: None,
+                gates: vec![],
+            },
+            Args::from_args(["-", "--frames", "frames"], dir)?
+        );
+        assert_eq!(
+            Args {
+                source: Source::default_for(dir),
+                frames: Some("/tmp/out".into()),
+                highlight: None,
+                gates: vec![],
+            },
+            Args::from_args(["--frames=/tmp/out"], dir)?
+        );
+        assert!(matches!(
+            Args::from_args(["--frames"], dir),
+            Err(InputError::MissingFramesDir)
+        ));
+        assert!(matches!(
+            Source::from_args(["--frames", "frames"], dir),
+            Err(InputError::UnexpectedArgument(arg)) if arg == "--frames"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_args() -> Result<(), InputError> {
+        let dir = Path::new("2024/day-24");
+        let options = ["--highlight", "--gates"];
+
+        let args = Args::with_options(
+            [
+                "--highlight",
+                "z00,z01",
+                "--gates=XOR",
+                "--highlight=",
+                "--gates",
+                "OR,AND",
+            ],
+            dir,
+            &options,
+        )?;
+        assert_eq!(Some(vec!["z00".into(), "z01".into()]), args.highlight);
+        assert_eq!(vec!["XOR", "OR", "AND"], args.gates);
+
+        assert_eq!(
+            Some(vec![]),
+            Args::with_options(["--highlight="], dir, &options)?.highlight
+        );
+        assert_eq!(None, Args::with_options(["-"], dir, &options)?.highlight);
+        assert!(matches!(
+            Args::with_options(["--gates"], dir, &options),
+            Err(InputError::MissingList(name)) if name == "--gates"
+        ));
+        assert!(matches!(
+            Args::with_options(["--gates", "XOR"], dir, &["--highlight"]),
+            Err(InputError::UnexpectedArgument(arg)) if arg == "--gates"
+        ));
+        assert!(matches!(
+            Args::from_args(["--highlight=z00"], dir),
+            Err(InputError::UnexpectedArgument(arg)) if arg == "--highlight=z00"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_path() {
+        assert_eq!(
+            Path::new("2024/day-23/input.dot"),
+            Source::default_for("2024/day-23").output_path("dot")
+        );
+        assert_eq!(
+            Path::new("/tmp/example.gif"),
+            Source::new("/tmp/example.txt").output_path("gif")
+        );
+        assert_eq!(Path::new("input.dot"), Source::Stdin.output_path("dot"));
+    }
+
+    #[test]
+    fn test_local_missing_input() -> Result<(), InputError> {
+        assert_eq!(None, local("does-not-exist/day-03")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let source = Source::default_for("does-not-exist/day-03");
+
+        match source.read() {
+            Err(InputError::Missing { path, day }) => {
+                assert_eq!(Path::new("does-not-exist/day-03/input.txt"), path);
+                assert_eq!("day-03", day);
+            }
+            other => panic!("expected a missing input error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Running nom parsers over puzzle input so that failures
+//! come back as miette diagnostics pointing at the offending
+//! byte, instead of a one-line `parse failed` message.
+//!
+//! Parsers take a [`Span`] and use the [`ErrorTree`] error
+//! type. Tags should come from [`nom_supreme::tag`] and
+//! sections can be named with
+//! [`ParserExt::context`](nom_supreme::ParserExt::context),
+//! both of which end up in the diagnostic. A failure that
+//! has nothing more specific to say than the nom error kind
+//! is labelled with its innermost context instead.
+
+use miette::{Diagnostic, SourceSpan};
+use nom::{character::complete::line_ending, Err, Parser};
+use nom_supreme::error::{BaseErrorKind, GenericErrorTree, StackContext};
+use thiserror::Error;
+
+/// Input type of the parsers, tracking the offset into the
+/// whole input.
+pub type Span<'a> = nom_locate::LocatedSpan<&'a str>;
+
+/// Error type of the parsers.
+pub type ErrorTree<'a> = nom_supreme::error::ErrorTree<Span<'a>>;
+
+/// Result of a parser over a [`Span`].
+pub type IResult<'a, O> = nom::IResult<Span<'a>, O, ErrorTree<'a>>;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+#[error("failed to parse the input")]
+#[diagnostic(code(aoc::parse), help("{help}"))]
+pub struct ParseError {
+    #[source_code]
+    input: String,
+    #[label("{label}")]
+    span: SourceSpan,
+    label: String,
+    help: String,
+}
+
+impl ParseError {
+    /// Byte offset of the failure in the input.
+    pub fn offset(&self) -> usize {
+        self.span.offset()
+    }
+
+    /// What the parser expected at [`ParseError::offset`],
+    /// e.g. `expected "Register A: "`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Runs `parser` over the whole of `input`. Everything but
+/// trailing whitespace has to be consumed.
+pub fn parse<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<Span<'a>, O, ErrorTree<'a>>,
+) -> Result<O, ParseError> {
+    match parser.parse(Span::new(input)) {
+        Ok((rest, output)) if rest.fragment().trim().is_empty() => Ok(output),
+        Ok((rest, _)) => {
+            let offset = rest.location_offset() + (rest.len() - rest.trim_start().len());
+            Err(ParseError {
+                input: input.to_string(),
+                span: (offset, 1).into(),
+                label: "unexpected input".to_string(),
+                help: "the input continues after the last thing that could be parsed".to_string(),
+            })
+        }
+        Err(Err::Error(tree) | Err::Failure(tree)) => Err(to_parse_error(input, &tree)),
+        Err(Err::Incomplete(_)) => Err(ParseError {
+            input: input.to_string(),
+            span: (input.len(), 0).into(),
+            label: "unexpected end of input".to_string(),
+            help: "the input ends early".to_string(),
+        }),
+    }
+}
+
+/// Like `separated_list1(separator, parser)`, except that an
+/// item after a separator that does not parse fails the list
+/// instead of ending it. The diagnostic then points into that
+/// item rather than at "unexpected input" after the last good
+/// one. A separator followed by nothing but whitespace ends
+/// the list.
+pub fn list1<'a, O, S>(
+    mut separator: impl Parser<Span<'a>, S, ErrorTree<'a>>,
+    mut parser: impl Parser<Span<'a>, O, ErrorTree<'a>>,
+) -> impl FnMut(Span<'a>) -> IResult<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = parser.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = separator.parse(input) {
+            if rest.fragment().trim().is_empty() {
+                break;
+            }
+            let (rest, item) = parser.parse(rest)?;
+            items.push(item);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// One `parser` per line, see [`list1`].
+pub fn lines<'a, O>(
+    parser: impl Parser<Span<'a>, O, ErrorTree<'a>>,
+) -> impl FnMut(Span<'a>) -> IResult<'a, Vec<O>> {
+    list1(line_ending, parser)
+}
+
+/// One leaf of the error tree, with the named sections it
+/// happened in from outermost to innermost.
+struct Failure {
+    offset: usize,
+    expected: String,
+    contexts: Vec<&'static str>,
+}
+
+/// Reports the failures that got furthest into the input, the
+/// ones before them are alternatives that were given up on.
+fn to_parse_error(input: &str, tree: &ErrorTree<'_>) -> ParseError {
+    let mut failures = vec![];
+    collect(tree, &[], &mut failures);
+
+    let offset = failures
+        .iter()
+        .map(|failure| failure.offset)
+        .max()
+        .unwrap_or_default();
+    let furthest: Vec<&Failure> = failures
+        .iter()
+        .filter(|failure| failure.offset == offset)
+        .collect();
+
+    let mut expected: Vec<&str> = vec![];
+    for failure in &furthest {
+        if !expected.contains(&failure.expected.as_str()) {
+            expected.push(&failure.expected);
+        }
+    }
+    let contexts = furthest
+        .iter()
+        .map(|failure| &failure.contexts)
+        .max_by_key(|contexts| contexts.len())
+        .filter(|contexts| !contexts.is_empty());
+
+    let width = input[offset..]
+        .chars()
+        .next()
+        .filter(|c| *c != '\n')
+        .map_or(0, char::len_utf8);
+    ParseError {
+        input: input.to_string(),
+        span: (offset, width).into(),
+        label: format!("expected {}", expected.join(" or ")),
+        help: match contexts {
+            Some(contexts) => format!("while parsing {}", contexts.join(" > ")),
+            None => "check the input at the highlighted position".to_string(),
+        },
+    }
+}
+
+fn collect(tree: &ErrorTree<'_>, outer: &[&'static str], failures: &mut Vec<Failure>) {
+    match tree {
+        GenericErrorTree::Base { location, kind } => failures.push(Failure {
+            offset: location.location_offset(),
+            expected: match kind {
+                BaseErrorKind::Expected(expectation) => expectation.to_string(),
+                // A bare nom error kind like `TakeWhile1` means
+                // little to a reader, the section it was in says
+                // more.
+                BaseErrorKind::Kind(kind) => outer.last().map_or_else(
+                    || kind.description().to_lowercase(),
+                    |context| context.to_string(),
+                ),
+                BaseErrorKind::External(error) => error.to_string(),
+            },
+            contexts: outer.to_vec(),
+        }),
+        GenericErrorTree::Stack { base, contexts } => {
+            // The stack lists the innermost context first.
+            let mut nested = outer.to_vec();
+            nested.extend(
+                contexts
+                    .iter()
+                    .rev()
+                    .filter_map(|(_, context)| match context {
+                        StackContext::Context(name) => Some(*name),
+                        StackContext::Kind(_) => None,
+                    }),
+            );
+            collect(base, &nested, failures);
+        }
+        GenericErrorTree::Alt(alternatives) => {
+            for alternative in alternatives {
+                collect(alternative, outer, failures);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{character::complete, sequence::preceded};
+    use nom_supreme::{tag::complete::tag, ParserExt};
+
+    fn register(input: Span) -> IResult<i32> {
+        preceded(tag("Register A: "), complete::i32)
+            .context("register A")
+            .parse(input)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(729), parse("Register A: 729\n", register));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("Register A: x729", register).unwrap_err();
+        assert_eq!(12, error.offset());
+        assert_eq!("expected an ascii digit", error.label());
+        assert_eq!("while parsing register A", error.help);
+
+        let error = parse("Register B: 729", register).unwrap_err();
+        assert_eq!(0, error.offset());
+        assert_eq!("expected \"Register A: \"", error.label());
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            Ok(vec![1, 2]),
+            parse("Register A: 1\nRegister A: 2\n", lines(register))
+        );
+
+        let error = parse(
+            "Register A: 1\nRegister A: 2\nRegister A: three\n",
+            lines(register),
+        )
+        .unwrap_err();
+        assert_eq!(40, error.offset());
+        assert_eq!("expected an ascii digit", error.label());
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let error = parse("Register A: 729\n  Register B: 0", register).unwrap_err();
+        assert_eq!(18, error.offset());
+        assert_eq!("unexpected input", error.label());
+    }
+}
@@ -0,0 +1,131 @@
+//! Known-correct answers from `answers.toml` at the root of
+//! the repository, checked by the regression suite in
+//! `tests/answers.rs`.
+
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+use miette::Diagnostic;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// File name of the recorded answers in the repository root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum AnswersError {
+    #[error("failed to read answers from {}", path.display())]
+    #[diagnostic(code(aoc::answers::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("answers are not valid toml")]
+    #[diagnostic(code(aoc::answers::parse))]
+    Parse(#[from] toml::de::Error),
+    #[error("`[{year}.{day}]` is not a valid year and day")]
+    #[diagnostic(
+        code(aoc::answers::key),
+        help("answers are keyed by year and day, e.g. `[2024.17]`")
+    )]
+    InvalidKey { year: String, day: String },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Expected answers keyed by year, day and part.
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: BTreeMap<(u16, u8), DayAnswers>,
+}
+
+impl Answers {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, AnswersError> {
+        let path = path.into();
+        let input = std::fs::read_to_string(&path)
+            .map_err(|source| AnswersError::Read { path, source })?;
+        Self::parse(&input)
+    }
+
+    /// Parses tables of the form
+    ///
+    /// ```toml
+    /// [2024.17]
+    /// part1 = "4,6,3,5,6,3,5,2,1,0"
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, AnswersError> {
+        let years: BTreeMap<String, BTreeMap<String, DayAnswers>> = toml::from_str(input)?;
+
+        let mut days = BTreeMap::new();
+        for (year, year_days) in years {
+            for (day, answers) in year_days {
+                let key = year.parse().ok().zip(day.parse().ok()).ok_or_else(|| {
+                    AnswersError::InvalidKey {
+                        year: year.clone(),
+                        day: day.clone(),
+                    }
+                })?;
+                days.insert(key, answers);
+            }
+        }
+
+        Ok(Self { days })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        let answers = self.days.get(&(year, day))?;
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), AnswersError> {
+        let answers = Answers::parse(
+            r#"
+[2015.7]
+part1 = "16076"
+
+[2024.17]
+part1 = "4,6,3,5,6,3,5,2,1,0"
+part2 = "117440"
+"#,
+        )?;
+
+        assert_eq!(Some("16076"), answers.get(2015, 7, 1));
+        assert_eq!(None, answers.get(2015, 7, 2));
+        assert_eq!(Some("4,6,3,5,6,3,5,2,1,0"), answers.get(2024, 17, 1));
+        assert_eq!(Some("117440"), answers.get(2024, 17, 2));
+        assert_eq!(None, answers.get(2024, 1, 1));
+        assert_eq!(None, answers.get(2024, 17, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Answers::parse("[2024.day-17]\npart1 = \"1\""),
+            Err(AnswersError::InvalidKey { day, .. }) if day == "day-17"
+        ));
+        assert!(matches!(
+            Answers::parse("[2024.17]\npart3 = \"1\""),
+            Err(AnswersError::Parse(_))
+        ));
+        assert!(matches!(
+            Answers::parse("[2024.17]\npart1 = 1"),
+            Err(AnswersError::Parse(_))
+        ));
+    }
+}
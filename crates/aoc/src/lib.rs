@@ -0,0 +1,20 @@
+use std::path::{Path, PathBuf};
+
+pub mod answers;
+pub mod registry;
+pub mod scaffold;
+
+/// Root of the repository, the directory holding the
+/// `2015` and `2024` workspaces.
+pub fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("crate to live in `crates/aoc`")
+        .to_path_buf()
+}
+
+/// Directory of a day crate, e.g. `2024/day-17`.
+pub fn day_dir(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string()).join(format!("day-{day:02}"))
+}
@@ -0,0 +1,335 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    registry::{self, Day, Process},
+    scaffold::{self, Change},
+};
+use aoc_client::{Client, Guesses, Outcome, FIXTURES_DIR, GUESSES_FILE, PUZZLE_FILE};
+use aoc_input::Source;
+use clap::{Parser, Subcommand};
+use miette::{miette, IntoDiagnostic, WrapErr};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run registered solvers against the local puzzle
+    /// inputs, e.g. `aoc run 2024 17 2` or `aoc run 2015
+    /// --all`
+    Run {
+        year: u16,
+        /// required unless `--all` is passed
+        #[arg(required_unless_present = "all", value_parser = parse_day)]
+        day: Option<u8>,
+        /// runs both parts when left out
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// run every registered day of the year
+        #[arg(long, conflicts_with_all = ["day", "part"])]
+        all: bool,
+        /// read the input from this file instead of the
+        /// day's `input.txt`, `-` reads stdin
+        #[arg(short, long, conflicts_with = "all")]
+        input: Option<PathBuf>,
+    },
+    /// Time every registered day against its local input and
+    /// print a table of median solve times per part. Build
+    /// with `--release`; `cargo bench -p day-NN` in a year
+    /// workspace breaks a day down into parse and process
+    Bench {
+        /// every year when left out
+        year: Option<u16>,
+        /// runs per part, the median is reported
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        samples: u32,
+    },
+    /// Download a day's puzzle input into
+    /// `<year>/day-NN/input.txt` unless it is already there,
+    /// using the session cookie in `SESSION`
+    Fetch {
+        year: u16,
+        #[arg(value_parser = parse_day)]
+        day: u8,
+    },
+    /// Write the examples and their answers from the day's
+    /// puzzle page into `<year>/day-NN/fixtures`, for the
+    /// day's tests to `include_str!`. The page is cached in
+    /// `puzzle.html` and only downloaded when missing
+    Examples {
+        year: u16,
+        #[arg(value_parser = parse_day)]
+        day: u8,
+        /// download the page again, e.g. to pick up part two
+        /// after solving part one
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Create `<year>/day-NN` from the year's `daily-template`
+    /// and register it with this runner. Safe to run again, an
+    /// existing day is never overwritten and is only
+    /// registered when it is missing
+    New {
+        year: u16,
+        #[arg(value_parser = parse_day)]
+        day: u8,
+        /// also download the input and write the example
+        /// fixtures
+        #[arg(long)]
+        fetch: bool,
+    },
+    /// Solve a part against the local input and submit the
+    /// answer. Every verdict is kept in the day's
+    /// `guesses.toml`, and answers that are known to be wrong
+    /// or outside the too high/too low bounds are never sent
+    Submit {
+        year: u16,
+        #[arg(value_parser = parse_day)]
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+}
+
+fn main() -> miette::Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            all,
+            input,
+        } => {
+            let days: Vec<&Day> = match (all, day) {
+                (true, _) => registry::year(year).collect(),
+                (false, Some(day)) => vec![registry::find(year, day)
+                    .ok_or_else(|| miette!("{year} day {day} is not registered"))?],
+                (false, None) => unreachable!("clap requires a day without --all"),
+            };
+            if days.is_empty() {
+                return Err(miette!("no days registered for {year}"));
+            }
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+
+            let root = aoc::repo_root();
+            let mut failures = 0;
+            for day in days {
+                let source = match &input {
+                    Some(path) => Source::new(path),
+                    None => Source::default_for(aoc::day_dir(&root, day.year, day.day)),
+                };
+                if let Err(report) = run(&source, day, &parts) {
+                    if !all {
+                        return Err(report);
+                    }
+                    eprintln!("{:?}", report);
+                    failures += 1;
+                }
+            }
+
+            if failures > 0 {
+                return Err(miette!("{failures} day(s) failed"));
+            }
+            Ok(())
+        }
+        Command::Bench { year, samples } => {
+            let days: Vec<&Day> = match year {
+                Some(year) => registry::year(year).collect(),
+                None => registry::DAYS.iter().collect(),
+            };
+            if days.is_empty() {
+                return Err(miette!(
+                    "no days registered for {}",
+                    year.unwrap_or_default()
+                ));
+            }
+
+            let root = aoc::repo_root();
+            println!("{:<6}{:<5}{:>12}{:>12}", "year", "day", "part 1", "part 2");
+            for day in days {
+                let Some(input) = aoc_input::local(aoc::day_dir(&root, day.year, day.day))? else {
+                    println!(
+                        "{:<6}{:<5}{:>12}{:>12}",
+                        day.year,
+                        format!("{:02}", day.day),
+                        "no input",
+                        "-"
+                    );
+                    continue;
+                };
+                let [part1, part2] = [1, 2].map(|part| {
+                    let process = day.part(part).expect("every day to have two parts");
+                    match median_solve_time(process, &input, samples) {
+                        Some(duration) => format_duration(duration),
+                        None => "failed".to_string(),
+                    }
+                });
+                println!(
+                    "{:<6}{:<5}{:>12}{:>12}",
+                    day.year,
+                    format!("{:02}", day.day),
+                    part1,
+                    part2
+                );
+            }
+            Ok(())
+        }
+        Command::Fetch { year, day } => fetch_input(year, day),
+        Command::Examples { year, day, refresh } => write_examples(year, day, refresh),
+        Command::New { year, day, fetch } => {
+            let root = aoc::repo_root();
+            let day_dir = aoc::day_dir(&root, year, day);
+
+            let created = scaffold::create_day(&root, year, day)?;
+            println!("{created} {}", day_dir.display());
+            for (path, change) in scaffold::register(&root, year, day)? {
+                let change = match change {
+                    Change::Created => "registered in",
+                    Change::Unchanged => "already in",
+                };
+                println!("{change} {}", path.display());
+            }
+
+            if fetch {
+                fetch_input(year, day)?;
+                write_examples(year, day, false)?;
+            }
+            Ok(())
+        }
+        Command::Submit { year, day, part } => {
+            let registered = registry::find(year, day)
+                .ok_or_else(|| miette!("{year} day {day} is not registered"))?;
+            let day_dir = aoc::day_dir(&aoc::repo_root(), year, day);
+            let label = format!("{year} day {day:02} part {part}");
+
+            let input = Source::default_for(&day_dir).read()?;
+            let process = registered
+                .part(part)
+                .expect("clap to only allow parts 1 and 2");
+            let answer = process(&input).wrap_err_with(|| format!("{label}: process"))?;
+
+            let guesses_path = day_dir.join(GUESSES_FILE);
+            let mut guesses = Guesses::load(&guesses_path)?;
+            guesses.check(part, &answer)?;
+
+            println!("{label}: submitting `{answer}`");
+            let outcome = Client::from_env()?.submit(year, day, part, &answer)?;
+            if let Outcome::Answered(verdict) = outcome {
+                guesses.record(part, &answer, verdict);
+                guesses.save(&guesses_path)?;
+            }
+            println!("{label}: {outcome}");
+            Ok(())
+        }
+    }
+}
+
+/// Downloads the day's input unless it is already there.
+fn fetch_input(year: u16, day: u8) -> miette::Result<()> {
+    let path = aoc::day_dir(&aoc::repo_root(), year, day).join(aoc_input::INPUT_FILE);
+    let fetched = Client::from_env()?.fetch_input_to(year, day, &path)?;
+    println!("{fetched} {}", path.display());
+    Ok(())
+}
+
+/// Writes the fixtures of a day from its cached puzzle page,
+/// downloading the page first when it is missing or `refresh`
+/// is set.
+fn write_examples(year: u16, day: u8, refresh: bool) -> miette::Result<()> {
+    let day_dir = aoc::day_dir(&aoc::repo_root(), year, day);
+    let page = day_dir.join(PUZZLE_FILE);
+    if refresh || !page.exists() {
+        let client = Client::from_env()?;
+        let fetched = if refresh {
+            client.refresh_puzzle_to(year, day, &page)?
+        } else {
+            client.fetch_puzzle_to(year, day, &page)?
+        };
+        println!("{fetched} {}", page.display());
+    }
+
+    let html = std::fs::read_to_string(&page)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to read {}", page.display()))?;
+    let parts = aoc_client::extract_parts(&html);
+    if parts.is_empty() {
+        return Err(miette!("no puzzle description in {}", page.display()));
+    }
+    for path in aoc_client::write_fixtures(&parts, &day_dir.join(FIXTURES_DIR))? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Accepts `17` as well as the `day-17` directory names used
+/// by the justfiles.
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.strip_prefix("day-").unwrap_or(day).parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("`{day}` is not a day between 1 and 25")),
+    }
+}
+
+/// Reads the day's input once and runs each requested part
+/// on it, stopping at the first failing part.
+fn run(source: &Source, day: &Day, parts: &[u8]) -> miette::Result<()> {
+    let start = Instant::now();
+    let input = source.read()?;
+    let read = start.elapsed();
+
+    for &part in parts {
+        let label = format!("{} day {:02} part {}", day.year, day.day, part);
+        let process = day
+            .part(part)
+            .ok_or_else(|| miette!("{label} does not exist"))?;
+
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| process(&input)))
+            .map_err(|_| miette!("{label}: solver panicked"))?
+            .wrap_err_with(|| format!("{label}: process"))?;
+        let solve = start.elapsed();
+
+        println!(
+            "{label}: {result} (read {}, solve {})",
+            format_duration(read),
+            format_duration(solve)
+        );
+    }
+    Ok(())
+}
+
+/// Median wall time of `samples` runs, `None` when the solver
+/// errors or panics on the input.
+fn median_solve_time(process: Process, input: &str, samples: u32) -> Option<Duration> {
+    let mut times = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| process(input)));
+            matches!(result, Ok(Ok(_))).then(|| start.elapsed())
+        })
+        .collect::<Option<Vec<_>>>()?;
+    times.sort();
+    Some(times[times.len() / 2])
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.as_micros() {
+        0 => format!("{}ns", duration.as_nanos()),
+        1..1_000 => format!("{}µs", duration.as_micros()),
+        1_000..1_000_000 => format!("{:.2}ms", duration.as_secs_f64() * 1_000.0),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
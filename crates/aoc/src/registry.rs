@@ -0,0 +1,131 @@
+//! Every day's `process` functions, keyed by year and day.
+
+/// Signature shared by every `partN::process` in the repo.
+pub type Process = fn(&str) -> miette::Result<String>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub part1: Process,
+    pub part2: Process,
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<Process> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $krate:ident) => {
+        Day {
+            year: $year,
+            day: $day,
+            part1: $krate::part1::process,
+            part2: $krate::part2::process,
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(2015, 1, y2015_day_01),
+    day!(2015, 2, y2015_day_02),
+    day!(2015, 3, y2015_day_03),
+    day!(2015, 4, y2015_day_04),
+    day!(2015, 5, y2015_day_05),
+    day!(2015, 6, y2015_day_06),
+    Day {
+        year: 2015,
+        day: 7,
+        part1: |input| y2015_day_07::part1::process(input, None),
+        part2: y2015_day_07::part2::process,
+    },
+    day!(2015, 8, y2015_day_08),
+    day!(2015, 9, y2015_day_09),
+    day!(2015, 10, y2015_day_10),
+    day!(2015, 11, y2015_day_11),
+    day!(2015, 12, y2015_day_12),
+    day!(2015, 13, y2015_day_13),
+    day!(2015, 14, y2015_day_14),
+    day!(2015, 15, y2015_day_15),
+    day!(2024, 1, y2024_day_01),
+    day!(2024, 2, y2024_day_02),
+    day!(2024, 3, y2024_day_03),
+    day!(2024, 4, y2024_day_04),
+    day!(2024, 5, y2024_day_05),
+    day!(2024, 6, y2024_day_06),
+    day!(2024, 7, y2024_day_07),
+    day!(2024, 8, y2024_day_08),
+    day!(2024, 9, y2024_day_09),
+    day!(2024, 10, y2024_day_10),
+    day!(2024, 11, y2024_day_11),
+    day!(2024, 12, y2024_day_12),
+    day!(2024, 13, y2024_day_13),
+    day!(2024, 14, y2024_day_14),
+    day!(2024, 15, y2024_day_15),
+    day!(2024, 16, y2024_day_16),
+    day!(2024, 17, y2024_day_17),
+    day!(2024, 18, y2024_day_18),
+    day!(2024, 19, y2024_day_19),
+    day!(2024, 20, y2024_day_20),
+    day!(2024, 21, y2024_day_21),
+    day!(2024, 22, y2024_day_22),
+    day!(2024, 23, y2024_day_23),
+    day!(2024, 24, y2024_day_24),
+    day!(2024, 25, y2024_day_25),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub fn year(year: u16) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique() {
+        for (i, a) in DAYS.iter().enumerate() {
+            assert!(
+                DAYS[i + 1..]
+                    .iter()
+                    .all(|b| (a.year, a.day) != (b.year, b.day)),
+                "{} day {} is registered twice",
+                a.year,
+                a.day
+            );
+        }
+    }
+
+    #[test]
+    fn test_find() -> miette::Result<()> {
+        let day = find(2015, 1).expect("2015 day 1 to be registered");
+        assert_eq!("3", (day.part1)("(()(()(")?);
+        assert_eq!("1", (day.part(2).unwrap())(")")?);
+        assert!(day.part(3).is_none());
+        assert!(find(2015, 26).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_year() {
+        for y in [2015, 2024] {
+            let days = year(y).map(|d| d.day).collect::<Vec<_>>();
+            assert!(!days.is_empty(), "{y} has no days");
+            assert!(
+                days.windows(2).all(|pair| pair[0] < pair[1]),
+                "{y} days are not sorted and unique: {days:?}"
+            );
+            assert!(days.iter().all(|day| (1..=25).contains(day)));
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! Creating a new day crate from its year's `daily-template`
+//! and registering it with the runner.
+//!
+//! Every step leaves finished work alone, so running it again
+//! after a failure picks up where it stopped: an existing day
+//! directory is never written to, and a day that is already
+//! registered is not added twice.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Directory of the template inside a year workspace.
+pub const TEMPLATE_DIR: &str = "daily-template";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ScaffoldError {
+    #[error("there is no {year} workspace at {}", path.display())]
+    #[diagnostic(
+        code(aoc::scaffold::workspace),
+        help("a year needs a cargo workspace with a `daily-template` before days can be added")
+    )]
+    NoWorkspace { year: u16, path: PathBuf },
+    #[error("{} exists but is not the crate `{package}`", path.display())]
+    #[diagnostic(
+        code(aoc::scaffold::not_a_day),
+        help("move the directory out of the way, it is never overwritten")
+    )]
+    NotADay { path: PathBuf, package: String },
+    #[error("could not find where to register the day in {}", path.display())]
+    #[diagnostic(code(aoc::scaffold::layout))]
+    UnknownLayout { path: PathBuf },
+    #[error("failed to read {}", path.display())]
+    #[diagnostic(code(aoc::scaffold::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::scaffold::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Whether a step had anything left to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Created,
+    Unchanged,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Created => write!(f, "created"),
+            Change::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+/// Package name of a day crate, e.g. `day-07`.
+pub fn package_name(day: u8) -> String {
+    format!("day-{day:02}")
+}
+
+/// Name the runner depends on a day crate under, e.g.
+/// `y2024_day_07`.
+pub fn dependency_name(year: u16, day: u8) -> String {
+    format!("y{year}_day_{day:02}")
+}
+
+/// Copies `<year>/daily-template` to `<year>/day-NN`, filling in
+/// the crate name. An existing day crate is left untouched.
+pub fn create_day(root: &Path, year: u16, day: u8) -> Result<Change, ScaffoldError> {
+    let workspace = root.join(year.to_string());
+    let template = workspace.join(TEMPLATE_DIR);
+    if !template.is_dir() {
+        return Err(ScaffoldError::NoWorkspace {
+            year,
+            path: workspace,
+        });
+    }
+
+    let package = package_name(day);
+    let day_dir = workspace.join(&package);
+    if day_dir.exists() {
+        let manifest = day_dir.join("Cargo.toml");
+        let is_day = std::fs::read_to_string(&manifest)
+            .is_ok_and(|manifest| manifest.contains(&format!("name = \"{package}\"")));
+        return match is_day {
+            true => Ok(Change::Unchanged),
+            false => Err(ScaffoldError::NotADay {
+                path: day_dir,
+                package,
+            }),
+        };
+    }
+
+    // Build the crate next to its final place and move it in
+    // once complete, so a failed copy never leaves a half day
+    // behind that the next run would refuse to touch.
+    let staging = workspace.join(format!(".{package}.new"));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).map_err(|source| ScaffoldError::Write {
+            path: staging.clone(),
+            source,
+        })?;
+    }
+    let replacements = [
+        ("{{project-name}}", package.clone()),
+        ("{{crate_name}}", package.replace('-', "_")),
+        ("day 00", format!("day {day:02}")),
+    ];
+    copy_template(&template, &staging, &replacements)?;
+    std::fs::rename(&staging, &day_dir).map_err(|source| ScaffoldError::Write {
+        path: day_dir,
+        source,
+    })?;
+    Ok(Change::Created)
+}
+
+fn copy_template(
+    from: &Path,
+    to: &Path,
+    replacements: &[(&str, String)],
+) -> Result<(), ScaffoldError> {
+    let read = |source| ScaffoldError::Read {
+        path: from.to_path_buf(),
+        source,
+    };
+    std::fs::create_dir_all(to).map_err(|source| ScaffoldError::Write {
+        path: to.to_path_buf(),
+        source,
+    })?;
+
+    for entry in std::fs::read_dir(from).map_err(read)? {
+        let entry = entry.map_err(read)?;
+        let source_path = entry.path();
+        let target_path = to.join(entry.file_name());
+        if entry.file_type().map_err(read)?.is_dir() {
+            copy_template(&source_path, &target_path, replacements)?;
+            continue;
+        }
+
+        let contents =
+            std::fs::read_to_string(&source_path).map_err(|source| ScaffoldError::Read {
+                path: source_path.clone(),
+                source,
+            })?;
+        let contents = replacements
+            .iter()
+            .fold(contents, |contents, (from, to)| contents.replace(from, to));
+        std::fs::write(&target_path, contents).map_err(|source| ScaffoldError::Write {
+            path: target_path.clone(),
+            source,
+        })?;
+    }
+    Ok(())
+}
+
+/// Adds the day crate to `crates/aoc/Cargo.toml` and to
+/// [`DAYS`](crate::registry::DAYS) in `crates/aoc/src/registry.rs`.
+pub fn register(root: &Path, year: u16, day: u8) -> Result<[(PathBuf, Change); 2], ScaffoldError> {
+    let crate_dir = root.join("crates").join("aoc");
+    let manifest = crate_dir.join("Cargo.toml");
+    let registry = crate_dir.join("src").join("registry.rs");
+
+    Ok([
+        (
+            manifest.clone(),
+            edit(&manifest, |text| add_dependency(text, year, day))?,
+        ),
+        (
+            registry.clone(),
+            edit(&registry, |text| add_registry_entry(text, year, day))?,
+        ),
+    ])
+}
+
+/// Rewrites `path` with `change`, which returns `Some(None)`
+/// when the file already has what it would add and `None` when
+/// it cannot tell where to add it.
+fn edit(
+    path: &Path,
+    change: impl FnOnce(&str) -> Option<Option<String>>,
+) -> Result<Change, ScaffoldError> {
+    let text = std::fs::read_to_string(path).map_err(|source| ScaffoldError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    match change(&text) {
+        None => Err(ScaffoldError::UnknownLayout {
+            path: path.to_path_buf(),
+        }),
+        Some(None) => Ok(Change::Unchanged),
+        Some(Some(text)) => {
+            std::fs::write(path, text).map_err(|source| ScaffoldError::Write {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            Ok(Change::Created)
+        }
+    }
+}
+
+/// Inserts the path dependency on the day crate, keeping the
+/// per-year blocks sorted. `Some(None)` when it is already
+/// there, `None` when the manifest has no `[dependencies]`.
+pub fn add_dependency(manifest: &str, year: u16, day: u8) -> Option<Option<String>> {
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let dependencies = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")?;
+    let entries: Vec<((u16, u8), usize)> = lines
+        .iter()
+        .enumerate()
+        .skip(dependencies)
+        .filter_map(|(i, line)| Some((dependency_key(line)?, i)))
+        .collect();
+    if entries.iter().any(|(key, _)| *key == (year, day)) {
+        return Some(None);
+    }
+
+    let package = package_name(day);
+    let line = format!(
+        "y{year}-day-{day:02} = {{ package = \"{package}\", path = \"../../{year}/{package}\" }}"
+    );
+    let same_year = |(y, _): &(u16, u8)| *y == year;
+    let before = entries.iter().rev().find(|(key, _)| *key < (year, day));
+    let after = entries.iter().find(|(key, _)| *key > (year, day));
+
+    match (before, after) {
+        (Some((key, i)), _) if same_year(key) => lines.insert(i + 1, &line),
+        (_, Some((key, i))) if same_year(key) => lines.insert(*i, &line),
+        // First day of a year starts its own block.
+        (Some((_, i)), _) => {
+            lines.insert(i + 1, "");
+            lines.insert(i + 2, &line);
+        }
+        (None, Some((_, i))) => {
+            lines.insert(*i, "");
+            lines.insert(*i, &line);
+        }
+        (None, None) => {
+            lines.push("");
+            lines.push(&line);
+        }
+    }
+    Some(Some(lines.join("\n") + "\n"))
+}
+
+/// `(2024, 7)` for `y2024-day-07 = { ... }`.
+fn dependency_key(line: &str) -> Option<(u16, u8)> {
+    let (name, _) = line.split_once(" = ")?;
+    let (year, day) = name.strip_prefix('y')?.split_once("-day-")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Inserts `day!(year, day, yYYYY_day_NN)` into `DAYS`, sorted
+/// by year and day. `Some(None)` when the day is already
+/// registered, `None` when there is no `DAYS` to add it to.
+pub fn add_registry_entry(source: &str, year: u16, day: u8) -> Option<Option<String>> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static DAYS"))?;
+    let end = lines[start..].iter().position(|line| *line == "];")? + start;
+
+    // `(key, first line, last line)` of every entry, either
+    // `day!(2015, 1, ...)` or a hand written `Day { ... }`.
+    let mut entries: Vec<((u16, u8), usize, usize)> = vec![];
+    let mut i = start + 1;
+    while i < end {
+        let line = lines[i].trim();
+        if let Some(args) = line.strip_prefix("day!(") {
+            let mut args = args.split(',').map(str::trim);
+            let key = (args.next()?.parse().ok()?, args.next()?.parse().ok()?);
+            entries.push((key, i, i));
+        } else if line == "Day {" {
+            let close = lines[i..end].iter().position(|line| line.trim() == "},")? + i;
+            let field = |name: &str| {
+                lines[i..close]
+                    .iter()
+                    .find_map(|line| line.trim().strip_prefix(name)?.strip_suffix(','))
+            };
+            let key = (
+                field("year: ")?.parse().ok()?,
+                field("day: ")?.parse().ok()?,
+            );
+            entries.push((key, i, close));
+            i = close;
+        }
+        i += 1;
+    }
+    if entries.iter().any(|(key, ..)| *key == (year, day)) {
+        return Some(None);
+    }
+
+    let line = format!("    day!({year}, {day}, {}),", dependency_name(year, day));
+    let at = match entries.iter().rev().find(|(key, ..)| *key < (year, day)) {
+        Some((_, _, last)) => last + 1,
+        None => entries.first().map_or(end, |(_, first, _)| *first),
+    };
+    lines.insert(at, &line);
+    Some(Some(lines.join("\n") + "\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "aoc"
+
+[dependencies]
+clap.workspace = true
+
+y2015-day-01 = { package = "day-01", path = "../../2015/day-01" }
+y2015-day-03 = { package = "day-03", path = "../../2015/day-03" }
+
+y2024-day-01 = { package = "day-01", path = "../../2024/day-01" }
+"#;
+
+    const REGISTRY: &str = r#"pub static DAYS: &[Day] = &[
+    day!(2015, 1, y2015_day_01),
+    Day {
+        year: 2015,
+        day: 7,
+        part1: |input| y2015_day_07::part1::process(input, None),
+        part2: y2015_day_07::part2::process,
+    },
+    day!(2024, 1, y2024_day_01),
+];
+
+pub fn find() {}
+"#;
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest = add_dependency(MANIFEST, 2015, 2).unwrap().unwrap();
+        assert!(manifest.contains(
+            "../../2015/day-01\" }\ny2015-day-02 = { package = \"day-02\", path = \"../../2015/day-02\" }\ny2015-day-03"
+        ));
+
+        let manifest = add_dependency(MANIFEST, 2016, 4).unwrap().unwrap();
+        assert!(manifest.contains("2015/day-03\" }\n\ny2016-day-04 = { package = \"day-04\", path = \"../../2016/day-04\" }\n\ny2024-day-01"));
+
+        let manifest = add_dependency(MANIFEST, 2024, 25).unwrap().unwrap();
+        assert!(manifest.ends_with("2024/day-01\" }\ny2024-day-25 = { package = \"day-25\", path = \"../../2024/day-25\" }\n"));
+
+        assert_eq!(None, add_dependency(MANIFEST, 2015, 3).unwrap());
+        assert_eq!(None, add_dependency("[package]\n", 2015, 3));
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let registry = add_registry_entry(REGISTRY, 2015, 8).unwrap().unwrap();
+        assert!(registry.contains(
+            "        part2: y2015_day_07::part2::process,\n    },\n    day!(2015, 8, y2015_day_08),\n    day!(2024, 1"
+        ));
+
+        let registry = add_registry_entry(REGISTRY, 2015, 3).unwrap().unwrap();
+        assert!(registry.contains("y2015_day_01),\n    day!(2015, 3, y2015_day_03),\n    Day {"));
+
+        let registry = add_registry_entry(REGISTRY, 2024, 2).unwrap().unwrap();
+        assert!(registry.contains("y2024_day_01),\n    day!(2024, 2, y2024_day_02),\n];"));
+
+        assert_eq!(None, add_registry_entry(REGISTRY, 2015, 7).unwrap());
+        assert_eq!(None, add_registry_entry(REGISTRY, 2024, 1).unwrap());
+        assert_eq!(None, add_registry_entry("fn main() {}\n", 2024, 1));
+    }
+
+    #[test]
+    fn test_create_day() -> Result<(), ScaffoldError> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let template = root.join("2024").join(TEMPLATE_DIR);
+        std::fs::create_dir_all(template.join("src").join("bin")).unwrap();
+        std::fs::write(
+            template.join("Cargo.toml"),
+            "[package]\nname = \"{{project-name}}\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            template.join("src").join("bin").join("part1.rs"),
+            "use {{crate_name}}::part1::process;\n// day 00\n",
+        )
+        .unwrap();
+
+        assert_eq!(Change::Created, create_day(&root, 2024, 7)?);
+        let day_dir = root.join("2024").join("day-07");
+        assert_eq!(
+            "use day_07::part1::process;\n// day 07\n",
+            std::fs::read_to_string(day_dir.join("src/bin/part1.rs")).unwrap()
+        );
+
+        std::fs::write(day_dir.join("src/bin/part1.rs"), "solved").unwrap();
+        assert_eq!(Change::Unchanged, create_day(&root, 2024, 7)?);
+        assert_eq!(
+            "solved",
+            std::fs::read_to_string(day_dir.join("src/bin/part1.rs")).unwrap()
+        );
+
+        std::fs::create_dir_all(root.join("2024").join("day-08")).unwrap();
+        assert!(matches!(
+            create_day(&root, 2024, 8),
+            Err(ScaffoldError::NotADay { .. })
+        ));
+        assert!(matches!(
+            create_day(&root, 2016, 1),
+            Err(ScaffoldError::NoWorkspace { year: 2016, .. })
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! Regression suite: runs every registered solver whose
+//! puzzle input is present locally and compares the result
+//! with `answers.toml`. Days without an input or without a
+//! recorded answer are skipped, so the suite passes on a
+//! fresh checkout.
+//!
+//! Some days are slow without optimisations, run it with
+//! `cargo test --release -p aoc --test answers`.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc::{
+    answers::{Answers, ANSWERS_FILE},
+    registry::DAYS,
+};
+
+#[test]
+fn recorded_answers() -> miette::Result<()> {
+    let root = aoc::repo_root();
+    let answers = Answers::load(root.join(ANSWERS_FILE))?;
+
+    let mut checked = 0;
+    let mut failures = vec![];
+    for day in DAYS {
+        let Some(input) = aoc_input::local(aoc::day_dir(&root, day.year, day.day))? else {
+            eprintln!("skipping {} day {:02}: no input", day.year, day.day);
+            continue;
+        };
+
+        for part in [1, 2] {
+            let label = format!("{} day {:02} part {}", day.year, day.day, part);
+            let Some(expected) = answers.get(day.year, day.day, part) else {
+                eprintln!("skipping {label}: no recorded answer");
+                continue;
+            };
+            let process = day.part(part).expect("every day to have two parts");
+
+            checked += 1;
+            match panic::catch_unwind(AssertUnwindSafe(|| process(&input))) {
+                Ok(Ok(result)) if result == expected => {}
+                Ok(Ok(result)) => {
+                    failures.push(format!("{label}: expected `{expected}`, got `{result}`"))
+                }
+                Ok(Err(report)) => failures.push(format!("{label}: {report}")),
+                Err(_) => failures.push(format!("{label}: solver panicked")),
+            }
+        }
+    }
+
+    eprintln!("checked {checked} recorded answer(s)");
+    assert!(
+        failures.is_empty(),
+        "{} answer(s) regressed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+    Ok(())
+}
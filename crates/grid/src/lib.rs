@@ -0,0 +1,399 @@
+//! Dense, row-major 2d grid indexed by [`IVec2`]
+//! positions, with `x` growing to the right and `y`
+//! growing downwards like the puzzle text.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use glam::IVec2;
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub mod path;
+
+/// Offsets to the four orthogonal neighbours.
+pub const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+
+/// Offsets to all eight neighbours, orthogonal first.
+pub const ALL_DIRECTIONS: [IVec2; 8] = [
+    IVec2::X,
+    IVec2::Y,
+    IVec2::NEG_X,
+    IVec2::NEG_Y,
+    IVec2::new(1, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+    IVec2::new(1, -1),
+];
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum GridError {
+    #[error("grid input is empty")]
+    #[diagnostic(code(grid::empty))]
+    Empty,
+    #[error("row {row} is {found} cells wide, expected {expected}")]
+    #[diagnostic(
+        code(grid::ragged),
+        help("every line of a grid must have the same length")
+    )]
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("unexpected `{value}` at {position}")]
+    #[diagnostic(code(grid::invalid_cell))]
+    InvalidCell { position: IVec2, value: char },
+    #[error("{cells} cells do not fill a {width}x{height} grid")]
+    #[diagnostic(code(grid::size))]
+    Size {
+        width: usize,
+        height: usize,
+        cells: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` x `height` cells all set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from row-major cells.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::Size {
+                width,
+                height,
+                cells: cells.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Builds a grid by calling `f` for every position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(IVec2) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| IVec2::new(x as i32, y as i32)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, mapping every character
+    /// to a cell.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Like [`Grid::parse`], but the mapper can reject a
+    /// character by returning `None`.
+    pub fn try_parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            let mut found = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or(GridError::InvalidCell {
+                    position: IVec2::new(x as i32, y as i32),
+                    value: c,
+                })?;
+                cells.push(cell);
+                found += 1;
+            }
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `(width, height)` as a vector.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn in_bounds(&self, position: IVec2) -> bool {
+        (0..self.width as i32).contains(&position.x)
+            && (0..self.height as i32).contains(&position.y)
+    }
+
+    fn index_of(&self, position: IVec2) -> Option<usize> {
+        self.in_bounds(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    fn position_of(&self, index: usize) -> IVec2 {
+        IVec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: IVec2) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Swaps two cells, panicking if either is out of
+    /// bounds.
+    pub fn swap(&mut self, a: IVec2, b: IVec2) {
+        let a = self.index_of(a).expect("position to be in bounds");
+        let b = self.index_of(b).expect("position to be in bounds");
+        self.cells.swap(a, b);
+    }
+
+    /// In-bounds orthogonal neighbours of `position`.
+    pub fn neighbors4(&self, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .map(move |dir| position + dir)
+            .filter(|next| self.in_bounds(*next))
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of
+    /// `position`.
+    pub fn neighbors8(&self, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        ALL_DIRECTIONS
+            .into_iter()
+            .map(move |dir| position + dir)
+            .filter(|next| self.in_bounds(*next))
+    }
+
+    /// First position, in reading order, whose cell
+    /// matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(&mut predicate)
+            .map(|index| self.position_of(index))
+    }
+
+    /// Every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + '_ {
+        (0..self.cells.len()).map(|index| self.position_of(index))
+    }
+
+    /// Every cell with its position in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.position_of(index), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (IVec2, &mut T)> + '_ {
+        let width = self.width;
+        self.cells.iter_mut().enumerate().map(move |(index, cell)| {
+            (
+                IVec2::new((index % width) as i32, (index / width) as i32),
+                cell,
+            )
+        })
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Every row, top to bottom. A grid with no columns
+    /// still has `height` rows, all of them empty.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        (x < self.width).then(|| self.cells[x..].iter().step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// New grid of the same size with every cell mapped
+    /// through `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: IVec2) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, position: IVec2) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position} is outside of the grid"))
+    }
+}
+
+/// Writes the grid back out as text, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#.#
+..S
+E##";
+
+    #[test]
+    fn test_parse() -> Result<(), GridError> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+
+        assert_eq!(IVec2::new(3, 3), grid.size());
+        assert_eq!(Some(&'S'), grid.get(IVec2::new(2, 1)));
+        assert_eq!('E', grid[IVec2::new(0, 2)]);
+        assert_eq!(None, grid.get(IVec2::new(3, 0)));
+        assert_eq!(None, grid.get(IVec2::new(0, -1)));
+        assert_eq!(INPUT, grid.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Err(GridError::Empty), Grid::parse("", |c| c));
+        assert_eq!(
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            }),
+            Grid::parse("#.#\n..", |c| c)
+        );
+        assert_eq!(
+            Err(GridError::InvalidCell {
+                position: IVec2::new(2, 1),
+                value: 'S'
+            }),
+            Grid::try_parse(INPUT, |c| (c != 'S').then_some(c))
+        );
+    }
+
+    #[test]
+    fn test_no_columns() -> Result<(), GridError> {
+        let grid = Grid::from_vec(0, 2, Vec::<char>::new())?;
+        assert_eq!(vec![&[] as &[char]; 2], grid.rows().collect::<Vec<_>>());
+        assert_eq!("\n", grid.to_string());
+        assert_eq!(3, Grid::new(0, 3, '.').rows().count());
+        assert_eq!(0, Grid::from_fn(0, 3, |_| '.').cells().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> Result<(), GridError> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+
+        let mut corner = grid.neighbors4(IVec2::ZERO).collect::<Vec<_>>();
+        corner.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(vec![IVec2::new(1, 0), IVec2::new(0, 1)], corner);
+        assert_eq!(4, grid.neighbors4(IVec2::ONE).count());
+        assert_eq!(3, grid.neighbors8(IVec2::ZERO).count());
+        assert_eq!(8, grid.neighbors8(IVec2::ONE).count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_and_iterators() -> Result<(), GridError> {
+        let mut grid = Grid::parse(INPUT, |c| c)?;
+
+        assert_eq!(Some(IVec2::new(2, 1)), grid.find(|c| *c == 'S'));
+        assert_eq!(None, grid.find(|c| *c == 'x'));
+        assert_eq!(Some(&['.', '.', 'S'][..]), grid.row(1));
+        assert_eq!(
+            vec!['#', 'S', '#'],
+            grid.column(2).unwrap().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["#.E", "..#", "#S#"],
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(4, grid.iter().filter(|(_, c)| **c == '#').count());
+
+        grid.swap(IVec2::new(2, 1), IVec2::new(1, 1));
+        grid[IVec2::ZERO] = '.';
+        assert_eq!("..#\n.S.\nE##", grid.to_string());
+
+        let walls = grid.map(|c| *c == '#');
+        assert_eq!(3, walls.cells().iter().filter(|wall| **wall).count());
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+//! Shortest paths over a [`Grid`], seen as a weighted graph
+//! of states.
+//!
+//! A state is where a walker is (and, for [`Heading`],
+//! which way it faces). [`GridGraph`] decides which cells
+//! can be entered and what moving and turning cost, and the
+//! searches run on `pathfinding` from there.
+//!
+//! ```
+//! use glam::IVec2;
+//! use grid::{path::{GridGraph, Heading}, Grid};
+//!
+//! let maze = Grid::parse("S.#\n#..\n#.E", |c| c == '#').unwrap();
+//! let graph = GridGraph::new(&maze, |_, wall| !wall).turn_cost(1000);
+//!
+//! let (_, cost) = graph
+//!     .shortest_path(Heading::new(IVec2::ZERO, IVec2::X), |state| {
+//!         state.position == IVec2::new(2, 2)
+//!     })
+//!     .unwrap();
+//! assert_eq!(2004, cost);
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+use glam::IVec2;
+use pathfinding::prelude::{astar_bag_collect, bfs_reach, dijkstra, dijkstra_all};
+
+use crate::{Grid, ORTHOGONAL};
+
+/// Something that stands on a cell of the grid and knows
+/// which states it can step to.
+pub trait State: Copy + Eq + Hash {
+    fn position(&self) -> IVec2;
+
+    /// Every state one step away with its cost, whether or
+    /// not its cell can be entered.
+    fn steps(self, costs: Costs) -> impl Iterator<Item = (Self, usize)>;
+}
+
+/// A bare position moves to its four orthogonal
+/// neighbours and never turns.
+impl State for IVec2 {
+    fn position(&self) -> IVec2 {
+        *self
+    }
+
+    fn steps(self, costs: Costs) -> impl Iterator<Item = (Self, usize)> {
+        ORTHOGONAL
+            .into_iter()
+            .map(move |dir| (self + dir, costs.step))
+    }
+}
+
+/// A position facing a direction, which moves forward or
+/// turns a quarter left or right on the spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Heading {
+    pub position: IVec2,
+    pub direction: IVec2,
+}
+
+impl Heading {
+    pub fn new(position: IVec2, direction: IVec2) -> Self {
+        Self {
+            position,
+            direction,
+        }
+    }
+}
+
+impl State for Heading {
+    fn position(&self) -> IVec2 {
+        self.position
+    }
+
+    fn steps(self, costs: Costs) -> impl Iterator<Item = (Self, usize)> {
+        let Heading {
+            position,
+            direction,
+        } = self;
+        [
+            (Heading::new(position + direction, direction), costs.step),
+            (Heading::new(position, direction.perp()), costs.turn),
+            (Heading::new(position, -direction.perp()), costs.turn),
+        ]
+        .into_iter()
+    }
+}
+
+/// What a single move and a quarter turn cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    pub step: usize,
+    pub turn: usize,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        Self { step: 1, turn: 1 }
+    }
+}
+
+/// A grid as a graph of `S` states, where a state can only
+/// be entered when `passable` accepts its cell. Cells
+/// outside of the grid are never passable.
+pub struct GridGraph<'a, T, S, P> {
+    grid: &'a Grid<T>,
+    passable: P,
+    costs: Costs,
+    state: PhantomData<S>,
+}
+
+impl<'a, T, S, P> GridGraph<'a, T, S, P>
+where
+    S: State,
+    P: Fn(IVec2, &T) -> bool,
+{
+    /// Graph with the default [`Costs`] of one per move and
+    /// one per turn.
+    pub fn new(grid: &'a Grid<T>, passable: P) -> Self {
+        Self {
+            grid,
+            passable,
+            costs: Costs::default(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn step_cost(mut self, cost: usize) -> Self {
+        self.costs.step = cost;
+        self
+    }
+
+    pub fn turn_cost(mut self, cost: usize) -> Self {
+        self.costs.turn = cost;
+        self
+    }
+
+    pub fn is_passable(&self, position: IVec2) -> bool {
+        self.grid
+            .get(position)
+            .is_some_and(|cell| (self.passable)(position, cell))
+    }
+
+    /// Passable states one step away from `state`, with the
+    /// cost of getting there.
+    pub fn successors(&self, state: &S) -> impl Iterator<Item = (S, usize)> + '_ {
+        state
+            .steps(self.costs)
+            .filter(|(next, _)| self.is_passable(next.position()))
+    }
+
+    /// A cheapest path from `start` to the first state
+    /// accepted by `goal`, both ends included, and its cost.
+    pub fn shortest_path(&self, start: S, goal: impl FnMut(&S) -> bool) -> Option<(Vec<S>, usize)> {
+        dijkstra(&start, |state| self.successors(state), goal)
+    }
+
+    /// Every path from `start` to a goal state that shares
+    /// the cheapest cost, and that cost.
+    pub fn all_shortest_paths(
+        &self,
+        start: S,
+        goal: impl FnMut(&S) -> bool,
+    ) -> Option<(Vec<Vec<S>>, usize)> {
+        astar_bag_collect(&start, |state| self.successors(state), |_| 0, goal)
+    }
+
+    /// Cost of the cheapest path from `start` to every state
+    /// it can reach, `start` itself included at zero. Empty
+    /// when `start` is off the maze or not passable.
+    pub fn distances(&self, start: S) -> HashMap<S, usize> {
+        if !self.is_passable(start.position()) {
+            return HashMap::new();
+        }
+        let mut distances = dijkstra_all(&start, |state| self.successors(state))
+            .into_iter()
+            .map(|(state, (_parent, cost))| (state, cost))
+            .collect::<HashMap<_, _>>();
+        distances.insert(start, 0);
+        distances
+    }
+
+    /// Like [`GridGraph::distances`], keeping the cheapest
+    /// cost per cell in a grid the size of the maze.
+    pub fn distance_grid(&self, start: S) -> Grid<Option<usize>> {
+        let mut grid: Grid<Option<usize>> = Grid::new(self.grid.width(), self.grid.height(), None);
+        for (state, cost) in self.distances(start) {
+            let cell = &mut grid[state.position()];
+            *cell = Some(cell.map_or(cost, |best| best.min(cost)));
+        }
+        grid
+    }
+
+    /// Every state that can be reached from `start`,
+    /// regardless of cost.
+    pub fn reachable(&self, start: S) -> HashSet<S> {
+        bfs_reach(start, |state| self.successors(state).map(|(next, _)| next)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "S..#
+.#..
+...E";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn test_positions() {
+        let maze = maze();
+        let graph = GridGraph::new(&maze, |_, wall| !wall);
+        let end = IVec2::new(3, 2);
+
+        let (path, cost) = graph.shortest_path(IVec2::ZERO, |&p| p == end).unwrap();
+        assert_eq!(5, cost);
+        assert_eq!((IVec2::ZERO, end), (path[0], path[5]));
+
+        let (paths, cost) = graph
+            .all_shortest_paths(IVec2::ZERO, |&p| p == end)
+            .unwrap();
+        assert_eq!(5, cost);
+        assert_eq!(3, paths.len());
+
+        let distances = graph.distance_grid(IVec2::ZERO);
+        assert_eq!(Some(5), distances[end]);
+        assert_eq!(None, distances[IVec2::new(3, 0)]);
+        assert_eq!(10, graph.reachable(IVec2::ZERO).len());
+    }
+
+    #[test]
+    fn test_headings() {
+        let maze = maze();
+        let graph = GridGraph::new(&maze, |_, wall| !wall).turn_cost(1000);
+        let start = Heading::new(IVec2::ZERO, IVec2::X);
+        let end = |state: &Heading| state.position == IVec2::new(3, 2);
+
+        let (path, cost) = graph.shortest_path(start, end).unwrap();
+        assert_eq!(2005, cost);
+        assert_eq!(IVec2::X, path.last().unwrap().direction);
+
+        let (paths, _) = graph.all_shortest_paths(start, end).unwrap();
+        assert_eq!(2, paths.len());
+        assert_eq!(Some(2005), graph.distance_grid(start)[IVec2::new(3, 2)]);
+    }
+
+    #[test]
+    fn test_passable_by_position() {
+        let maze = maze();
+        let wall = IVec2::new(1, 1);
+        let graph = GridGraph::new(&maze, |position, blocked| position == wall || !blocked);
+
+        assert!(graph.is_passable(wall));
+        assert!(!graph.is_passable(IVec2::new(3, 0)));
+        assert!(!graph.is_passable(IVec2::new(4, 0)));
+        assert_eq!(11, graph.reachable(IVec2::ZERO).len());
+    }
+
+    #[test]
+    fn test_distances_from_outside() {
+        let maze = maze();
+        let graph = GridGraph::new(&maze, |_, wall| !wall);
+
+        for start in [IVec2::new(4, 0), IVec2::new(-1, -1), IVec2::new(3, 0)] {
+            assert!(graph.distances(start).is_empty());
+            let grid = graph.distance_grid(start);
+            assert!(grid.cells().iter().all(Option::is_none));
+        }
+        assert_eq!(Some(5), graph.distance_grid(IVec2::ZERO)[IVec2::new(3, 2)]);
+    }
+}
@@ -0,0 +1,178 @@
+//! GraphViz export of a [`Netlist`], for looking at a
+//! circuit instead of reading its gate list.
+//!
+//! Every wire is a node labelled with the gate driving it,
+//! and every input of a gate an edge into that node.
+//! Constants are boxes and overridden wires are dashed.
+//!
+//! ```
+//! use netlist::{Gate, Netlist, Signal};
+//!
+//! let mut netlist = Netlist::new(1).unwrap();
+//! netlist.set("x", 1).unwrap();
+//! netlist.connect("z", Gate::Not(Signal::wire("x"))).unwrap();
+//!
+//! let dot = netlist.dot().highlight_wires(["z"]).to_string();
+//! assert!(dot.contains("\"x\" -> \"z\""));
+//! ```
+
+use std::{collections::BTreeSet, fmt};
+
+use crate::{Gate, Netlist, Signal};
+
+/// A [`Netlist`] as a GraphViz `digraph`, written by its
+/// [`Display`](fmt::Display) implementation.
+#[derive(Debug, Clone)]
+pub struct Dot<'a> {
+    netlist: &'a Netlist,
+    wires: BTreeSet<String>,
+    gates: BTreeSet<String>,
+}
+
+impl Netlist {
+    /// The circuit as a GraphViz graph, nothing highlighted.
+    pub fn dot(&self) -> Dot<'_> {
+        Dot {
+            netlist: self,
+            wires: BTreeSet::new(),
+            gates: BTreeSet::new(),
+        }
+    }
+}
+
+impl Dot<'_> {
+    /// Fills the nodes of `wires`, for example the suspects
+    /// of a miswired circuit.
+    pub fn highlight_wires<I>(mut self, wires: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.wires.extend(wires.into_iter().map(Into::into));
+        self
+    }
+
+    /// Outlines every wire driven by one of the gate types,
+    /// named as in [`Gate::name`], `XOR`.
+    pub fn highlight_gates<I>(mut self, gates: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.gates.extend(gates.into_iter().map(Into::into));
+        self
+    }
+
+    fn attributes(&self, wire: &str, gate: &Gate) -> Vec<String> {
+        let mut attributes = vec![];
+        let mut styles = vec![];
+        match self.netlist.overrides.get(wire) {
+            Some(value) => {
+                attributes.push(format!("label = \"{wire} = {value}\""));
+                styles.push("dashed");
+            }
+            None => match gate {
+                Gate::Buffer(Signal::Value(value)) => {
+                    attributes.push(format!("label = \"{wire} = {value}\""));
+                    attributes.push("shape = box".to_string());
+                }
+                _ => {
+                    let constants = gate
+                        .inputs()
+                        .filter_map(|signal| match signal {
+                            Signal::Value(value) => Some(format!(" {value}")),
+                            Signal::Wire(_) => None,
+                        })
+                        .collect::<String>();
+                    let bits = match gate {
+                        Gate::Shl(_, bits) | Gate::Shr(_, bits) => format!(" {bits}"),
+                        _ => String::new(),
+                    };
+                    attributes.push(format!(
+                        "label = \"{wire}\\n{}{constants}{bits}\"",
+                        gate.name()
+                    ));
+                }
+            },
+        }
+        if self.wires.contains(wire) {
+            styles.push("filled");
+            attributes.push("fillcolor = gold".to_string());
+        }
+        if self.gates.contains(gate.name()) {
+            attributes.push("color = red".to_string());
+            attributes.push("penwidth = 2".to_string());
+        }
+        if !styles.is_empty() {
+            attributes.push(format!("style = \"{}\"", styles.join(",")));
+        }
+        attributes
+    }
+}
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut gates = self.netlist.gates().collect::<Vec<_>>();
+        gates.sort_by_key(|(wire, _)| *wire);
+
+        writeln!(f, "digraph {{")?;
+        writeln!(f, "    rankdir = LR")?;
+        for (wire, gate) in &gates {
+            writeln!(
+                f,
+                "    \"{wire}\" [ {} ]",
+                self.attributes(wire, gate).join(", ")
+            )?;
+        }
+        for (wire, gate) in &gates {
+            if self.netlist.overrides.contains_key(*wire) {
+                continue;
+            }
+            for input in gate.input_wires() {
+                writeln!(f, "    \"{input}\" -> \"{wire}\"")?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot() {
+        let mut netlist = Netlist::new(16).unwrap();
+        for (wire, gate) in [
+            ("x", Gate::Buffer(Signal::Value(123))),
+            ("y", Gate::Buffer(Signal::wire("x"))),
+            ("f", Gate::Shl(Signal::wire("x"), 2)),
+            ("d", Gate::And(Signal::Value(1), Signal::wire("y"))),
+            ("e", Gate::Or(Signal::wire("x"), Signal::wire("y"))),
+        ] {
+            netlist.connect(wire, gate).unwrap();
+        }
+        netlist.override_wire("y", 7);
+
+        assert_eq!(
+            r#"digraph {
+    rankdir = LR
+    "d" [ label = "d\nAND 1", fillcolor = gold, style = "filled" ]
+    "e" [ label = "e\nOR", color = red, penwidth = 2 ]
+    "f" [ label = "f\nLSHIFT 2" ]
+    "x" [ label = "x = 123", shape = box ]
+    "y" [ label = "y = 7", fillcolor = gold, style = "dashed,filled" ]
+    "y" -> "d"
+    "x" -> "e"
+    "y" -> "e"
+    "x" -> "f"
+}
+"#,
+            netlist
+                .dot()
+                .highlight_wires(["d", "y"])
+                .highlight_gates(["OR"])
+                .to_string()
+        );
+    }
+}
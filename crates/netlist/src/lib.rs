@@ -0,0 +1,509 @@
+//! Combinational logic circuits: named wires, each driven by
+//! one gate, carrying unsigned values of a fixed bit width.
+//!
+//! Wires are evaluated in topological order of their
+//! inputs, so a wire that (indirectly) feeds itself or reads
+//! a wire nothing drives is reported instead of recursing
+//! forever or panicking.
+//!
+//! ```
+//! use netlist::{Gate, Netlist, Signal};
+//!
+//! let mut netlist = Netlist::new(16).unwrap();
+//! netlist.connect("x", Gate::Buffer(Signal::Value(123))).unwrap();
+//! netlist.connect("h", Gate::Not(Signal::wire("x"))).unwrap();
+//!
+//! assert_eq!(65412, netlist.value("h").unwrap());
+//! netlist.override_wire("x", 0);
+//! assert_eq!(65535, netlist.value("h").unwrap());
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub mod dot;
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum NetlistError {
+    #[error("wires are {width} bits wide, expected 1 to 64")]
+    #[diagnostic(code(netlist::width))]
+    Width { width: u32 },
+    #[error("wire `{wire}` is driven more than once")]
+    #[diagnostic(
+        code(netlist::multiple_drivers),
+        help("every wire can only be the output of a single gate")
+    )]
+    MultipleDrivers { wire: String },
+    #[error("wire `{wire}` does not exist")]
+    #[diagnostic(code(netlist::unknown_wire))]
+    UnknownWire { wire: String },
+    #[error("wire `{wire}` reads `{input}`, which nothing drives")]
+    #[diagnostic(code(netlist::dangling))]
+    Dangling { wire: String, input: String },
+    #[error("wires {} feed back into themselves", wires.join(" -> "))]
+    #[diagnostic(
+        code(netlist::cycle),
+        help("a combinational circuit needs every wire's inputs to settle before it does")
+    )]
+    Cycle { wires: Vec<String> },
+}
+
+/// An input of a gate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Signal {
+    Wire(String),
+    Value(u64),
+}
+
+impl Signal {
+    pub fn wire(name: impl Into<String>) -> Self {
+        Signal::Wire(name.into())
+    }
+
+    fn as_wire(&self) -> Option<&str> {
+        match self {
+            Signal::Wire(wire) => Some(wire),
+            Signal::Value(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Wire(wire) => write!(f, "{wire}"),
+            Signal::Value(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// What drives a wire.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Gate {
+    Buffer(Signal),
+    Not(Signal),
+    And(Signal, Signal),
+    Or(Signal, Signal),
+    Xor(Signal, Signal),
+    /// Shifts left by a fixed number of bits, dropping the
+    /// bits that leave the wire.
+    Shl(Signal, u32),
+    Shr(Signal, u32),
+}
+
+impl Gate {
+    pub fn inputs(&self) -> impl Iterator<Item = &Signal> {
+        let (a, b) = match self {
+            Gate::Buffer(a) | Gate::Not(a) | Gate::Shl(a, _) | Gate::Shr(a, _) => (a, None),
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::Xor(a, b) => (a, Some(b)),
+        };
+        std::iter::once(a).chain(b)
+    }
+
+    /// The gate type as the 2015 puzzle writes it, `AND`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gate::Buffer(_) => "BUF",
+            Gate::Not(_) => "NOT",
+            Gate::And(..) => "AND",
+            Gate::Or(..) => "OR",
+            Gate::Xor(..) => "XOR",
+            Gate::Shl(..) => "LSHIFT",
+            Gate::Shr(..) => "RSHIFT",
+        }
+    }
+
+    /// Wires read by the gate.
+    pub fn input_wires(&self) -> impl Iterator<Item = &str> {
+        self.inputs().filter_map(Signal::as_wire)
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gate::Buffer(a) => write!(f, "{a}"),
+            Gate::Not(a) => write!(f, "NOT {a}"),
+            Gate::And(a, b) => write!(f, "{a} AND {b}"),
+            Gate::Or(a, b) => write!(f, "{a} OR {b}"),
+            Gate::Xor(a, b) => write!(f, "{a} XOR {b}"),
+            Gate::Shl(a, bits) => write!(f, "{a} LSHIFT {bits}"),
+            Gate::Shr(a, bits) => write!(f, "{a} RSHIFT {bits}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Netlist {
+    width: u32,
+    drivers: HashMap<String, Gate>,
+    overrides: HashMap<String, u64>,
+}
+
+impl Netlist {
+    /// Empty circuit with wires of `width` bits.
+    pub fn new(width: u32) -> Result<Self, NetlistError> {
+        if !(1..=64).contains(&width) {
+            return Err(NetlistError::Width { width });
+        }
+        Ok(Self {
+            width,
+            drivers: HashMap::new(),
+            overrides: HashMap::new(),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    /// Drives `wire` with `gate`.
+    pub fn connect(&mut self, wire: impl Into<String>, gate: Gate) -> Result<(), NetlistError> {
+        let wire = wire.into();
+        if self.drivers.contains_key(&wire) {
+            return Err(NetlistError::MultipleDrivers { wire });
+        }
+        self.drivers.insert(wire, gate);
+        Ok(())
+    }
+
+    /// Drives `wire` with a constant.
+    pub fn set(&mut self, wire: impl Into<String>, value: u64) -> Result<(), NetlistError> {
+        self.connect(wire, Gate::Buffer(Signal::Value(value)))
+    }
+
+    /// Exchanges the gates driving `a` and `b`, for repairing
+    /// a circuit whose outputs were mixed up.
+    pub fn swap_drivers(&mut self, a: &str, b: &str) -> Result<(), NetlistError> {
+        for wire in [a, b] {
+            if !self.drivers.contains_key(wire) {
+                return Err(NetlistError::UnknownWire {
+                    wire: wire.to_string(),
+                });
+            }
+        }
+        if a == b {
+            return Ok(());
+        }
+        let gate_a = self.drivers.remove(a).expect("driven wire");
+        let gate_b = self
+            .drivers
+            .insert(b.to_string(), gate_a)
+            .expect("driven wire");
+        self.drivers.insert(a.to_string(), gate_b);
+        Ok(())
+    }
+
+    /// Forces `wire` to `value`, ignoring its gate, until
+    /// [`Netlist::clear_overrides`].
+    pub fn override_wire(&mut self, wire: impl Into<String>, value: u64) {
+        self.overrides.insert(wire.into(), value & self.mask());
+    }
+
+    pub fn clear_overrides(&mut self) {
+        self.overrides.clear();
+    }
+
+    /// Gate driving `wire`, if anything does.
+    pub fn driver(&self, wire: &str) -> Option<&Gate> {
+        self.drivers.get(wire)
+    }
+
+    /// Every driven wire with its gate, in no particular
+    /// order.
+    pub fn gates(&self) -> impl Iterator<Item = (&str, &Gate)> {
+        self.drivers
+            .iter()
+            .map(|(wire, gate)| (wire.as_str(), gate))
+    }
+
+    /// Every driven wire, in no particular order.
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.drivers.keys().map(String::as_str)
+    }
+
+    /// Value of a single wire, evaluating only its fan-in
+    /// cone.
+    pub fn value(&self, wire: &str) -> Result<u64, NetlistError> {
+        let order = self.order([wire])?;
+        Ok(self.evaluate_in(&order)[wire])
+    }
+
+    /// Values of every wire.
+    pub fn evaluate(&self) -> Result<HashMap<String, u64>, NetlistError> {
+        let order = self.topological_order()?;
+        Ok(self
+            .evaluate_in(&order)
+            .into_iter()
+            .map(|(wire, value)| (wire.to_string(), value))
+            .collect())
+    }
+
+    /// Every wire after all of its inputs. Overridden wires
+    /// do not depend on anything.
+    pub fn topological_order(&self) -> Result<Vec<&str>, NetlistError> {
+        let mut wires = self.drivers.keys().map(String::as_str).collect::<Vec<_>>();
+        wires.sort();
+        self.order(wires)
+    }
+
+    /// Every wire that `wire` depends on, directly or
+    /// through other wires, not including itself.
+    pub fn fan_in<'a>(&'a self, wire: &'a str) -> Result<BTreeSet<&'a str>, NetlistError> {
+        let mut cone = self.order([wire])?.into_iter().collect::<BTreeSet<_>>();
+        cone.remove(wire);
+        Ok(cone)
+    }
+
+    /// Inputs of `wire` that still count, none when it is
+    /// overridden.
+    fn inputs_of<'a>(&'a self, wire: &str) -> Option<Vec<&'a str>> {
+        if self.overrides.contains_key(wire) {
+            return Some(vec![]);
+        }
+        self.drivers
+            .get(wire)
+            .map(|gate| gate.input_wires().collect())
+    }
+
+    /// Depth-first post-order from `roots`, which lists
+    /// every wire after its inputs.
+    fn order<'a>(
+        &'a self,
+        roots: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<&'a str>, NetlistError> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            Visiting,
+            Done,
+        }
+
+        let mut marks: HashMap<&str, Mark> = HashMap::new();
+        let mut order = vec![];
+
+        for root in roots {
+            let root = self
+                .drivers
+                .get_key_value(root)
+                .map(|(wire, _)| wire.as_str())
+                .or_else(|| {
+                    self.overrides
+                        .get_key_value(root)
+                        .map(|(wire, _)| wire.as_str())
+                })
+                .ok_or_else(|| NetlistError::UnknownWire {
+                    wire: root.to_string(),
+                })?;
+            if marks.contains_key(root) {
+                continue;
+            }
+
+            // (wire, inputs still to visit)
+            let mut stack = vec![(root, self.inputs_of(root).unwrap_or_default())];
+            marks.insert(root, Mark::Visiting);
+
+            while let Some((wire, inputs)) = stack.last_mut() {
+                let wire = *wire;
+                let Some(input) = inputs.pop() else {
+                    marks.insert(wire, Mark::Done);
+                    order.push(wire);
+                    stack.pop();
+                    continue;
+                };
+
+                match marks.get(input) {
+                    Some(Mark::Done) => {}
+                    Some(Mark::Visiting) => {
+                        let start = stack
+                            .iter()
+                            .position(|(visiting, _)| *visiting == input)
+                            .expect("a visiting wire to be on the stack");
+                        let mut wires = stack[start..]
+                            .iter()
+                            .map(|(wire, _)| wire.to_string())
+                            .collect::<Vec<_>>();
+                        wires.push(input.to_string());
+                        return Err(NetlistError::Cycle { wires });
+                    }
+                    None => {
+                        let Some(inputs) = self.inputs_of(input) else {
+                            return Err(NetlistError::Dangling {
+                                wire: wire.to_string(),
+                                input: input.to_string(),
+                            });
+                        };
+                        marks.insert(input, Mark::Visiting);
+                        stack.push((input, inputs));
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Values of the wires in `order`, which has to list
+    /// every wire after its inputs.
+    fn evaluate_in<'a>(&self, order: &[&'a str]) -> HashMap<&'a str, u64> {
+        let mask = self.mask();
+        let mut values: HashMap<&str, u64> = HashMap::with_capacity(order.len());
+
+        for &wire in order {
+            let value = if let Some(&value) = self.overrides.get(wire) {
+                value
+            } else {
+                let read = |signal: &Signal| match signal {
+                    Signal::Wire(input) => values[input.as_str()],
+                    Signal::Value(value) => value & mask,
+                };
+                match &self.drivers[wire] {
+                    Gate::Buffer(a) => read(a),
+                    Gate::Not(a) => !read(a) & mask,
+                    Gate::And(a, b) => read(a) & read(b),
+                    Gate::Or(a, b) => read(a) | read(b),
+                    Gate::Xor(a, b) => read(a) ^ read(b),
+                    Gate::Shl(a, bits) => read(a).checked_shl(*bits).unwrap_or(0) & mask,
+                    Gate::Shr(a, bits) => read(a).checked_shr(*bits).unwrap_or(0),
+                }
+            };
+            values.insert(wire, value);
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example circuit of 2015 day 7.
+    fn example() -> Netlist {
+        let mut netlist = Netlist::new(16).unwrap();
+        for (wire, gate) in [
+            ("x", Gate::Buffer(Signal::Value(123))),
+            ("y", Gate::Buffer(Signal::Value(456))),
+            ("d", Gate::And(Signal::wire("x"), Signal::wire("y"))),
+            ("e", Gate::Or(Signal::wire("x"), Signal::wire("y"))),
+            ("f", Gate::Shl(Signal::wire("x"), 2)),
+            ("g", Gate::Shr(Signal::wire("y"), 2)),
+            ("h", Gate::Not(Signal::wire("x"))),
+            ("i", Gate::Not(Signal::wire("y"))),
+        ] {
+            netlist.connect(wire, gate).unwrap();
+        }
+        netlist
+    }
+
+    #[test]
+    fn test_evaluate() -> Result<(), NetlistError> {
+        let netlist = example();
+        let values = netlist.evaluate()?;
+
+        for (wire, value) in [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ] {
+            assert_eq!(value, values[wire], "wire {wire}");
+        }
+        assert_eq!(8, netlist.topological_order()?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_width() -> Result<(), NetlistError> {
+        let mut netlist = Netlist::new(1)?;
+        netlist.set("x", 1)?;
+        netlist.connect("z", Gate::Not(Signal::wire("x")))?;
+        assert_eq!(0, netlist.value("z")?);
+
+        assert_eq!(Err(NetlistError::Width { width: 0 }), Netlist::new(0));
+        assert_eq!(Err(NetlistError::Width { width: 65 }), Netlist::new(65));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overrides_and_fan_in() -> Result<(), NetlistError> {
+        let mut netlist = example();
+        netlist.connect("a", Gate::Or(Signal::wire("d"), Signal::wire("g")))?;
+
+        assert_eq!(BTreeSet::from(["d", "g", "x", "y"]), netlist.fan_in("a")?);
+        assert_eq!(72 | 114, netlist.value("a")?);
+
+        netlist.override_wire("d", 1);
+        assert_eq!(BTreeSet::from(["d", "g", "y"]), netlist.fan_in("a")?);
+        assert_eq!(1 | 114, netlist.value("a")?);
+
+        netlist.clear_overrides();
+        assert_eq!(72 | 114, netlist.value("a")?);
+
+        netlist.swap_drivers("d", "g")?;
+        assert_eq!(Some(&Gate::Shr(Signal::wire("y"), 2)), netlist.driver("d"));
+        assert_eq!(72 | 114, netlist.value("a")?);
+        assert_eq!(
+            Err(NetlistError::UnknownWire { wire: "q".into() }),
+            netlist.swap_drivers("d", "q")
+        );
+
+        netlist.swap_drivers("d", "d")?;
+        assert_eq!(Some(&Gate::Shr(Signal::wire("y"), 2)), netlist.driver("d"));
+        assert_eq!(
+            Err(NetlistError::UnknownWire { wire: "q".into() }),
+            netlist.swap_drivers("q", "q")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() -> Result<(), NetlistError> {
+        let mut netlist = example();
+        assert_eq!(
+            Err(NetlistError::MultipleDrivers { wire: "x".into() }),
+            netlist.set("x", 1)
+        );
+        assert_eq!(
+            Err(NetlistError::UnknownWire { wire: "a".into() }),
+            netlist.value("a")
+        );
+
+        netlist.connect("a", Gate::And(Signal::wire("d"), Signal::wire("q")))?;
+        assert_eq!(
+            Err(NetlistError::Dangling {
+                wire: "a".into(),
+                input: "q".into()
+            }),
+            netlist.value("a")
+        );
+        assert_eq!(72, netlist.value("d")?);
+
+        netlist.connect("q", Gate::Not(Signal::wire("b")))?;
+        netlist.connect("b", Gate::Buffer(Signal::wire("q")))?;
+        assert_eq!(
+            Err(NetlistError::Cycle {
+                wires: vec!["q".into(), "b".into(), "q".into()]
+            }),
+            netlist.value("a")
+        );
+        assert!(matches!(
+            netlist.evaluate(),
+            Err(NetlistError::Cycle { .. })
+        ));
+
+        netlist.override_wire("b", 7);
+        assert_eq!(72 & !7 & 0xffff, netlist.value("a")?);
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+//! Still and animated pictures of grid states, for write-ups
+//! where a terminal will not do.
+//!
+//! Every cell becomes a `scale` × `scale` square in the colour
+//! a palette picks for it. A [`Recorder`] writes each frame it
+//! is given as a numbered PNG into a directory and collects
+//! them all in an animated GIF next to them.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use grid::Grid;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::Simulation;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// File name of the animation inside a [`Recorder`]'s
+/// directory.
+pub const ANIMATION_FILE: &str = "animation.gif";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ImageError {
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(simulation::image::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to encode a PNG")]
+    #[diagnostic(code(simulation::image::png))]
+    Png(#[from] png::EncodingError),
+    #[error("failed to encode a GIF")]
+    #[diagnostic(code(simulation::image::gif))]
+    Gif(#[from] gif::EncodingError),
+    #[error("a {width}×{height} image is too large for a GIF")]
+    #[diagnostic(
+        code(simulation::image::size),
+        help("GIFs are at most 65535 pixels wide and high, try a smaller scale")
+    )]
+    TooLarge { width: usize, height: usize },
+    #[error("frame {frame} is {width}×{height}, but the animation is {expected_width}×{expected_height}")]
+    #[diagnostic(code(simulation::image::size))]
+    SizeChanged {
+        frame: usize,
+        width: usize,
+        height: usize,
+        expected_width: usize,
+        expected_height: usize,
+    },
+    #[error("frame {frame} has {colours} colours, a GIF frame at most 256")]
+    #[diagnostic(code(simulation::image::colours))]
+    TooManyColours { frame: usize, colours: usize },
+}
+
+/// Colours of the characters a [`Simulation`] renders, with
+/// one for every character that is not listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colours: HashMap<char, Rgb>,
+    default: Rgb,
+}
+
+impl Palette {
+    pub fn new(default: Rgb) -> Self {
+        Self {
+            colours: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Paints every character of `cells` in `colour`.
+    pub fn with(mut self, cells: &str, colour: Rgb) -> Self {
+        self.colours
+            .extend(cells.chars().map(|cell| (cell, colour)));
+        self
+    }
+
+    pub fn colour(&self, cell: &char) -> Rgb {
+        self.colours.get(cell).copied().unwrap_or(self.default)
+    }
+}
+
+/// A grid as rows of pixels, each cell `scale` pixels wide
+/// and high.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    fn new<T>(grid: &Grid<T>, palette: impl Fn(&T) -> Rgb, scale: usize) -> Self {
+        let width = grid.width() * scale;
+        let mut pixels = Vec::with_capacity(width * grid.height() * scale);
+        for row in grid.rows() {
+            let line = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(palette(cell), scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width,
+            height: grid.height() * scale,
+            pixels,
+        }
+    }
+
+    fn write_png(&self, path: &Path) -> Result<(), ImageError> {
+        let file = File::create(path).map_err(|source| ImageError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(file), self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// The pixels as indices into a palette of the colours
+    /// they use, or how many colours there are when a GIF
+    /// frame cannot hold them.
+    fn indexed(&self) -> Result<(Vec<u8>, Vec<u8>), usize> {
+        let mut indices = HashMap::new();
+        let mut palette = vec![];
+        let pixels = self
+            .pixels
+            .iter()
+            .map(|colour| {
+                *indices.entry(*colour).or_insert_with(|| {
+                    palette.extend_from_slice(colour);
+                    palette.len() / 3 - 1
+                })
+            })
+            .collect::<Vec<_>>();
+        if indices.len() > 256 {
+            return Err(indices.len());
+        }
+        Ok((
+            pixels.into_iter().map(|index| index as u8).collect(),
+            palette,
+        ))
+    }
+}
+
+/// Writes `grid` as a PNG, `scale` pixels per cell.
+pub fn write_png<T>(
+    path: impl AsRef<Path>,
+    grid: &Grid<T>,
+    palette: impl Fn(&T) -> Rgb,
+    scale: usize,
+) -> Result<(), ImageError> {
+    Image::new(grid, palette, scale).write_png(path.as_ref())
+}
+
+/// Writes frames into a directory as `00000.png`,
+/// `00001.png`, ... and as one animation in
+/// [`ANIMATION_FILE`].
+pub struct Recorder {
+    dir: PathBuf,
+    scale: usize,
+    delay: Duration,
+    frames: usize,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+    size: (usize, usize),
+}
+
+impl Recorder {
+    /// Records into `dir`, creating it when missing, at one
+    /// pixel per cell and 10 frames per second.
+    pub fn create(dir: impl Into<PathBuf>) -> Result<Self, ImageError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|source| ImageError::Io {
+            path: dir.clone(),
+            source,
+        })?;
+        Ok(Self {
+            dir,
+            scale: 1,
+            delay: Duration::from_millis(100),
+            frames: 0,
+            gif: None,
+            size: (0, 0),
+        })
+    }
+
+    /// Pixels per cell.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// How long the animation shows each frame, in steps of
+    /// 10ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Frames written so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Writes `grid` as the next frame.
+    pub fn record<T>(
+        &mut self,
+        grid: &Grid<T>,
+        palette: impl Fn(&T) -> Rgb,
+    ) -> Result<(), ImageError> {
+        let image = Image::new(grid, palette, self.scale);
+        image.write_png(&self.dir.join(format!("{:05}.png", self.frames)))?;
+
+        let (pixels, colours) = image
+            .indexed()
+            .map_err(|colours| ImageError::TooManyColours {
+                frame: self.frames,
+                colours,
+            })?;
+        let delay = (self.delay.as_millis() / 10) as u16;
+        let gif = match &mut self.gif {
+            Some(gif) => {
+                if self.size != (image.width, image.height) {
+                    return Err(ImageError::SizeChanged {
+                        frame: self.frames,
+                        width: image.width,
+                        height: image.height,
+                        expected_width: self.size.0,
+                        expected_height: self.size.1,
+                    });
+                }
+                gif
+            }
+            None => self.start_gif(image.width, image.height)?,
+        };
+        let mut frame = gif::Frame::from_palette_pixels(
+            image.width as u16,
+            image.height as u16,
+            pixels,
+            colours,
+            None,
+        );
+        frame.delay = delay;
+        gif.write_frame(&frame)?;
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn start_gif(
+        &mut self,
+        width: usize,
+        height: usize,
+    ) -> Result<&mut gif::Encoder<BufWriter<File>>, ImageError> {
+        let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(ImageError::TooLarge { width, height });
+        };
+        let path = self.dir.join(ANIMATION_FILE);
+        let file = File::create(&path).map_err(|source| ImageError::Io { path, source })?;
+        let mut gif = gif::Encoder::new(BufWriter::new(file), gif_width, gif_height, &[])?;
+        gif.set_repeat(gif::Repeat::Infinite)?;
+        self.size = (width, height);
+        Ok(self.gif.insert(gif))
+    }
+
+    /// Records the current state of `simulation` and the one
+    /// after each step, for at most `steps` steps. Returns the
+    /// steps taken, fewer when the simulation finished.
+    pub fn record_simulation<S: Simulation>(
+        &mut self,
+        simulation: &mut S,
+        palette: &Palette,
+        steps: usize,
+    ) -> Result<usize, ImageError> {
+        self.record(&simulation.render(), |cell| palette.colour(cell))?;
+        for taken in 0..steps {
+            if !simulation.step() {
+                return Ok(taken);
+            }
+            self.record(&simulation.render(), |cell| palette.colour(cell))?;
+        }
+        Ok(steps)
+    }
+
+    /// Completes the animation, returning how many frames
+    /// were written.
+    pub fn finish(self) -> Result<usize, ImageError> {
+        if let Some(gif) = self.gif {
+            gif.into_inner().map_err(|source| ImageError::Io {
+                path: self.dir.join(ANIMATION_FILE),
+                source,
+            })?;
+        }
+        Ok(self.frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::Counter;
+
+    #[test]
+    fn test_recorder() -> Result<(), ImageError> {
+        let dir = std::env::temp_dir().join(format!("simulation-image-{}", std::process::id()));
+        let palette = Palette::new([0, 0, 0]).with("#", [255, 200, 0]);
+
+        let mut recorder = Recorder::create(&dir)?.scale(2);
+        let steps = recorder.record_simulation(&mut Counter { value: 1, end: 3 }, &palette, 5)?;
+        assert_eq!(2, steps);
+        assert_eq!(3, recorder.finish()?);
+
+        // The second frame is `##.`, two pixels per cell.
+        let decoder = png::Decoder::new(File::open(dir.join("00001.png")).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((6, 2), (info.width, info.height));
+        assert_eq!(
+            [255, 200, 0, 255, 200, 0, 255, 200, 0, 255, 200, 0, 0, 0, 0, 0, 0, 0],
+            pixels[..18]
+        );
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(dir.join(ANIMATION_FILE)).unwrap())
+            .unwrap();
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(3, frames);
+
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Puzzles that move things around a grid one step at a
+//! time, and a player for watching them do it.
+//!
+//! A day implements [`Simulation`] for its state, solves the
+//! puzzle with [`Simulation::run`] and hands the same state
+//! to [`play`] in a `play` binary to show every step, or to
+//! an [`image::Recorder`] for pictures of them.
+
+use grid::Grid;
+
+pub mod image;
+pub mod player;
+pub mod terminal;
+
+pub use player::{Command, Player};
+pub use terminal::{play, PlayerError};
+
+/// State of a puzzle that advances in discrete steps.
+pub trait Simulation {
+    /// Advances by one step, `false` when the simulation
+    /// has already finished and nothing changed.
+    fn step(&mut self) -> bool;
+
+    /// The current state as one character per cell.
+    fn render(&self) -> Grid<char>;
+
+    /// A line about the current state, shown below the
+    /// grid.
+    fn status(&self) -> String {
+        String::new()
+    }
+
+    /// Steps until the simulation finishes, returning how
+    /// many steps it took.
+    fn run(&mut self) -> usize {
+        let mut steps = 0;
+        while self.step() {
+            steps += 1;
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A counter from 0 to `end`, drawn as a row of cells.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Counter {
+        pub value: usize,
+        pub end: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.end {
+                return false;
+            }
+            self.value += 1;
+            true
+        }
+
+        fn render(&self) -> Grid<char> {
+            Grid::from_fn(self.end, 1, |position| {
+                if (position.x as usize) < self.value {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+        }
+
+        fn status(&self) -> String {
+            format!("value {}", self.value)
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut counter = Counter { value: 2, end: 5 };
+        assert_eq!(3, counter.run());
+        assert_eq!("#####", counter.render().to_string());
+        assert_eq!(0, counter.run());
+    }
+}
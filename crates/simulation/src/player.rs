@@ -0,0 +1,293 @@
+//! Playback state of the player, apart from the terminal so
+//! that it can be driven without one.
+
+use std::time::Duration;
+
+use crate::Simulation;
+
+/// Steps per second the player can run at.
+const SPEEDS: [usize; 10] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+
+/// Shortest time a frame is shown while playing. Faster
+/// speeds take several steps per frame instead.
+const MIN_FRAME: Duration = Duration::from_millis(50);
+
+/// Most steps a jump takes per frame, so that a far jump on
+/// a simulation that never finishes keeps the player
+/// responsive and can be interrupted.
+const JUMP_STEPS: usize = 10_000;
+
+/// What the viewer asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    TogglePlay,
+    /// One step forward, pausing playback.
+    Step,
+    /// One step back, pausing playback.
+    Back,
+    Faster,
+    Slower,
+    JumpTo(usize),
+}
+
+/// A simulation with its playback position. Going back
+/// replays from a copy of the initial state, so a
+/// simulation does not need to undo its steps.
+#[derive(Debug, Clone)]
+pub struct Player<S> {
+    initial: S,
+    current: S,
+    step: usize,
+    finished: bool,
+    playing: bool,
+    speed: usize,
+    /// Where an unfinished jump is heading.
+    target: Option<usize>,
+}
+
+impl<S: Simulation + Clone> Player<S> {
+    /// Paused at the start, at 10 steps per second.
+    pub fn new(simulation: S) -> Self {
+        Self {
+            initial: simulation.clone(),
+            current: simulation,
+            step: 0,
+            finished: false,
+            playing: false,
+            speed: 3,
+            target: None,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Steps taken from the initial state.
+    pub fn position(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Whether a jump is still on its way, see
+    /// [`Player::jump_to`].
+    pub fn is_jumping(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Whether the simulation refused to take another step.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Steps per second while playing.
+    pub fn speed(&self) -> usize {
+        SPEEDS[self.speed]
+    }
+
+    /// Every command but a new jump stops the current one
+    /// where it got to.
+    pub fn apply(&mut self, command: Command) {
+        self.target = None;
+        match command {
+            Command::TogglePlay => self.playing = !self.playing && !self.finished,
+            Command::Step => {
+                self.playing = false;
+                self.advance(1);
+            }
+            Command::Back => {
+                self.playing = false;
+                self.jump_to(self.step.saturating_sub(1));
+            }
+            Command::Faster => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            Command::Slower => self.speed = self.speed.saturating_sub(1),
+            Command::JumpTo(step) => {
+                self.playing = false;
+                self.jump_to(step);
+            }
+        }
+    }
+
+    /// How long to show a frame for while playing.
+    pub fn delay(&self) -> Duration {
+        (Duration::from_secs(1) / self.speed() as u32).max(MIN_FRAME)
+    }
+
+    /// Advances by the steps one frame holds at the current
+    /// speed, pausing once the simulation finishes. Carries
+    /// on with an unfinished jump instead while there is one.
+    pub fn tick(&mut self) {
+        if self.target.is_some() {
+            self.continue_jump();
+            return;
+        }
+        if !self.playing {
+            return;
+        }
+        let steps = self.speed() as u128 * self.delay().as_millis() / 1000;
+        self.advance(steps.max(1) as usize);
+        if self.finished {
+            self.playing = false;
+        }
+    }
+
+    /// Goes to `step`, replaying from the start when it lies
+    /// behind. Stops early when the simulation finishes
+    /// before reaching it. Jumps longer than [`JUMP_STEPS`]
+    /// go on over the next [`Player::tick`]s.
+    pub fn jump_to(&mut self, step: usize) {
+        if step < self.step {
+            self.current = self.initial.clone();
+            self.step = 0;
+            self.finished = false;
+        }
+        self.target = Some(step);
+        self.continue_jump();
+    }
+
+    fn continue_jump(&mut self) {
+        let Some(target) = self.target else {
+            return;
+        };
+        self.advance((target - self.step).min(JUMP_STEPS));
+        if self.step == target || self.finished {
+            self.target = None;
+        }
+    }
+
+    fn advance(&mut self, steps: usize) {
+        for _ in 0..steps {
+            if !self.current.step() {
+                self.finished = true;
+                break;
+            }
+            self.step += 1;
+        }
+    }
+
+    /// The current grid with a status line below it.
+    pub fn frame(&self) -> String {
+        let state = if let Some(target) = self.target {
+            format!("jumping to {target}")
+        } else if self.finished {
+            "finished".to_string()
+        } else if self.playing {
+            "playing".to_string()
+        } else {
+            "paused".to_string()
+        };
+        let mut frame = format!(
+            "{}\nstep {} | {state} | {}/s",
+            self.current.render(),
+            self.step,
+            self.speed()
+        );
+        let status = self.current.status();
+        if !status.is_empty() {
+            frame.push_str(" | ");
+            frame.push_str(&status);
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::Counter;
+
+    #[test]
+    fn test_stepping() {
+        let mut player = Player::new(Counter { value: 0, end: 4 });
+
+        player.apply(Command::Step);
+        player.apply(Command::Step);
+        assert_eq!((2, 2), (player.position(), player.current().value));
+
+        player.apply(Command::Back);
+        assert_eq!((1, 1), (player.position(), player.current().value));
+
+        player.apply(Command::JumpTo(10));
+        assert_eq!((4, 4), (player.position(), player.current().value));
+        assert!(player.is_finished());
+
+        player.apply(Command::JumpTo(0));
+        assert_eq!((0, 0), (player.position(), player.current().value));
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn test_playing() {
+        let mut player = Player::new(Counter { value: 0, end: 30 });
+        assert_eq!(10, player.speed());
+        assert_eq!(Duration::from_millis(100), player.delay());
+
+        player.tick();
+        assert_eq!(0, player.position());
+
+        player.apply(Command::TogglePlay);
+        player.tick();
+        assert_eq!(1, player.position());
+
+        // 100 steps per second show every fifth step at 20
+        // frames per second.
+        for _ in 0..3 {
+            player.apply(Command::Faster);
+        }
+        assert_eq!(100, player.speed());
+        assert_eq!(MIN_FRAME, player.delay());
+        player.tick();
+        assert_eq!(6, player.position());
+
+        for _ in 0..20 {
+            player.apply(Command::Faster);
+        }
+        assert_eq!(1000, player.speed());
+        for _ in 0..20 {
+            player.apply(Command::Slower);
+        }
+        assert_eq!(1, player.speed());
+
+        player.jump_to(30);
+        player.tick();
+        assert!(player.is_finished());
+        assert!(!player.is_playing());
+        assert_eq!(
+            format!("{}\nstep 30 | finished | 1/s | value 30", "#".repeat(30)),
+            player.frame()
+        );
+    }
+
+    #[test]
+    fn test_long_jump() {
+        let end = 3 * JUMP_STEPS;
+        let mut player = Player::new(Counter { value: 0, end });
+
+        player.apply(Command::JumpTo(end + 5));
+        assert_eq!(JUMP_STEPS, player.position());
+        assert!(player.is_jumping());
+        assert!(player.frame().contains(&format!("jumping to {}", end + 5)));
+
+        player.tick();
+        player.tick();
+        assert_eq!(end, player.position());
+        assert!(player.is_jumping());
+        player.tick();
+        assert!(player.is_finished());
+        assert!(!player.is_jumping());
+
+        // Going back replays from the start, and stepping
+        // stops the jump where it got to.
+        player.apply(Command::Back);
+        assert_eq!(JUMP_STEPS, player.position());
+        player.apply(Command::Step);
+        assert_eq!(JUMP_STEPS + 1, player.position());
+        assert!(!player.is_jumping());
+        player.tick();
+        assert_eq!(JUMP_STEPS + 1, player.position());
+    }
+}
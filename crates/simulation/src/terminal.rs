@@ -0,0 +1,124 @@
+//! The [`Player`] in a terminal, drawn with crossterm.
+
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{Command, Player, Simulation};
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum PlayerError {
+    #[error("failed to draw the player in the terminal")]
+    #[diagnostic(
+        code(simulation::terminal),
+        help("the player needs an interactive terminal")
+    )]
+    Terminal(#[from] io::Error),
+}
+
+const KEYS: &str = "space play/pause | right step | left back | +/- speed | g jump | q quit";
+
+/// Plays `simulation` in the terminal, paused at the start,
+/// until `q` or `Esc` is pressed.
+pub fn play<S: Simulation + Clone>(simulation: S) -> Result<(), PlayerError> {
+    let mut player = Player::new(simulation);
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = run(&mut player, &mut stdout);
+    // Give the terminal back even when drawing failed.
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn run<S: Simulation + Clone>(
+    player: &mut Player<S>,
+    out: &mut impl Write,
+) -> Result<(), PlayerError> {
+    // Digits typed after `g`, until `Enter` jumps to them.
+    let mut jump: Option<String> = None;
+
+    loop {
+        draw(out, &player.frame(), jump.as_deref())?;
+
+        // A long jump goes on between frames until a key
+        // interrupts it.
+        let wait = if player.is_jumping() {
+            Duration::ZERO
+        } else {
+            player.delay()
+        };
+        if (player.is_playing() || player.is_jumping()) && !event::poll(wait)? {
+            player.tick();
+            continue;
+        }
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        if let Some(digits) = &mut jump {
+            match code {
+                KeyCode::Char(c) if c.is_ascii_digit() => digits.push(c),
+                KeyCode::Backspace => {
+                    digits.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(step) = digits.parse() {
+                        player.apply(Command::JumpTo(step));
+                    }
+                    jump = None;
+                }
+                KeyCode::Esc => jump = None,
+                _ => {}
+            }
+            continue;
+        }
+
+        let command = match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char(' ') => Command::TogglePlay,
+            KeyCode::Right | KeyCode::Char('n') => Command::Step,
+            KeyCode::Left | KeyCode::Char('b') => Command::Back,
+            KeyCode::Up | KeyCode::Char('+' | '=') => Command::Faster,
+            KeyCode::Down | KeyCode::Char('-') => Command::Slower,
+            KeyCode::Char('g') => {
+                jump = Some(String::new());
+                continue;
+            }
+            _ => continue,
+        };
+        player.apply(command);
+    }
+}
+
+fn draw(out: &mut impl Write, frame: &str, jump: Option<&str>) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+    for line in frame.lines() {
+        queue!(out, Print(line), cursor::MoveToNextLine(1))?;
+    }
+    match jump {
+        Some(digits) => queue!(out, Print(format!("jump to step: {digits}")))?,
+        None => queue!(out, Print(KEYS))?,
+    }
+    out.flush()
+}
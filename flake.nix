@@ -47,7 +47,6 @@
           cargo-deny
           cargo-edit
           cargo-watch
-          cargo-generate
           cargo-nextest
           rust-analyzer
         ];